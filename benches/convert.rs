@@ -0,0 +1,41 @@
+/*
+    Timings of the digit conversions, run with `cargo bench`
+    The bytes cases produce thousands of digits, where per-digit string shifting would dominate
+*/
+
+use converter::{bytes_to_base, convert_value, dec_to_base};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn dec_to_base_bench(c: &mut Criterion) {
+    c.bench_function("dec_to_base u128::MAX base 2", |b| {
+        b.iter(|| dec_to_base(black_box(u128::MAX), 2))
+    });
+    c.bench_function("dec_to_base u128::MAX base 62", |b| {
+        b.iter(|| dec_to_base(black_box(u128::MAX), 62))
+    });
+}
+
+fn convert_value_bench(c: &mut Criterion) {
+    let ones: String = "1".repeat(128);
+    c.bench_function("convert_value 128 binary digits", |b| {
+        b.iter(|| convert_value((2, 10), black_box(&ones)))
+    });
+}
+
+fn bytes_to_base_bench(c: &mut Criterion) {
+    let bytes: Vec<u8> = vec![0xa5; 1000];
+    c.bench_function("bytes_to_base 1000 bytes base 2", |b| {
+        b.iter(|| bytes_to_base(black_box(&bytes), 2))
+    });
+    c.bench_function("bytes_to_base 1000 bytes base 36", |b| {
+        b.iter(|| bytes_to_base(black_box(&bytes), 36))
+    });
+}
+
+criterion_group!(
+    benches,
+    dec_to_base_bench,
+    convert_value_bench,
+    bytes_to_base_bench
+);
+criterion_main!(benches);
@@ -0,0 +1,94 @@
+/*
+    Arbitrary precision conversions backed by num_bigint::BigUint
+    Enabled with the `bignum` feature; convert_value routes values past a u128 through here
+*/
+
+use crate::{digit_to_char, digit_value, strip_base_prefix, ConvertError, MAX_BASE};
+use num_bigint::BigUint;
+
+/// Parses `in_val` written in `base` into an arbitrarily large integer
+pub fn base_to_dec(in_val: &str, base: usize) -> Result<BigUint, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    if in_val.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+
+    // accumulate most significant digit first so no powers are needed
+    let mut out_val = BigUint::from(0u8);
+    let in_str: &str = strip_base_prefix(in_val, base);
+    let prefix_len: usize = in_val.len() - in_str.len();
+    for (i, c) in in_str.chars().enumerate() {
+        out_val = out_val * base + digit_value(c, prefix_len + i, base)?;
+    }
+    Ok(out_val)
+}
+
+/// Renders `in_dec` as a string of digits in `base`
+pub fn dec_to_base(in_dec: &BigUint, base: usize) -> Result<String, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    // base <= MAX_BASE so every radix digit fits a digit char
+    Ok(in_dec
+        .to_radix_be(base as u32)
+        .into_iter()
+        .map(|d| digit_to_char(d).unwrap())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert_value;
+
+    const HUNDRED_DIGITS: &str = "1234567890123456789012345678901234567890\
+        1234567890123456789012345678901234567890\
+        12345678901234567890";
+
+    #[test]
+    fn bignum_round_trip_test() {
+        assert_eq!(HUNDRED_DIGITS.len(), 100);
+
+        let b36: String = convert_value((10, 36), HUNDRED_DIGITS).unwrap();
+        assert_eq!(convert_value((36, 10), &b36).unwrap(), HUNDRED_DIGITS);
+
+        let neg: String = String::from("-") + HUNDRED_DIGITS;
+        let neg_b36: String = convert_value((10, 36), &neg).unwrap();
+        assert_eq!(convert_value((36, 10), &neg_b36).unwrap(), neg);
+    }
+
+    #[test]
+    fn bignum_long_value_test() {
+        // 2^10000 - 1 is ten thousand ones in binary
+        let big: BigUint = (BigUint::from(1u8) << 10000u32) - 1u8;
+        let binary: String = dec_to_base(&big, 2).unwrap();
+        assert_eq!(binary, "1".repeat(10000));
+        assert_eq!(base_to_dec(&binary, 2).unwrap(), big);
+
+        let decimal: String = convert_value((2, 10), &binary).unwrap();
+        assert_eq!(decimal.len(), 3011);
+        assert_eq!(convert_value((10, 2), &decimal).unwrap(), binary);
+    }
+
+    #[test]
+    fn bignum_base_to_dec_test() {
+        let big: BigUint = base_to_dec(HUNDRED_DIGITS, 10).unwrap();
+        assert_eq!(dec_to_base(&big, 10).unwrap(), HUNDRED_DIGITS);
+        assert_eq!(
+            base_to_dec("0xffffffffffffffffffffffffffffffff", 16).unwrap(),
+            BigUint::from(u128::MAX)
+        );
+        assert_eq!(dec_to_base(&BigUint::from(3843u16), 62).unwrap(), "ZZ");
+        assert_eq!(
+            base_to_dec("12g", 16),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 2,
+                base: 16
+            })
+        );
+        assert_eq!(base_to_dec("12", 100), Err(ConvertError::InvalidBase(100)));
+    }
+}
@@ -0,0 +1,335 @@
+/*
+    Byte buffers as one big-endian integer, for compact encodings
+    Unlike formatting each byte on its own, the whole buffer's value is converted,
+    eg. [1, 0] is 256 -> `74` in base 36
+    Leading zero bytes carry no value, so each one is kept as a leading `0` digit
+    Hex digits can also be split into their bytes in either order, eg. 11223344 -> 44 33 22 11
+    Text is converted a byte at a time instead, each of its UTF-8 bytes padded to the digits
+    of 255, eg. Hi -> 4869 in base 16
+*/
+
+use crate::{
+    dec_to_base, digit_count, digit_to_char, digit_value, pad_digits, ConvertError, MAX_BASE,
+};
+
+/// Renders the big-endian integer held in `bytes` in `base`, eg. `[255, 255]` -> `1ekf` in base 36
+///
+/// Each leading zero byte becomes a leading `0` digit, and an empty buffer an empty string.
+pub fn bytes_to_base(bytes: &[u8], base: usize) -> Result<String, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+
+    let zeros: usize = bytes.iter().take_while(|b| **b == 0).count();
+    // digits of the value so far, least significant first
+    let mut digits: Vec<u8> = Vec::new();
+    for b in &bytes[zeros..] {
+        // multiply the digits by 256 and add the byte, carrying upward
+        let mut carry: usize = usize::from(*b);
+        for d in digits.iter_mut() {
+            carry += usize::from(*d) << 8;
+            *d = (carry % base) as u8;
+            carry /= base;
+        }
+        while carry > 0 {
+            digits.push((carry % base) as u8);
+            carry /= base;
+        }
+    }
+
+    let mut output: String = "0".repeat(zeros);
+    // every digit is below base, so it always maps to a char
+    output.extend(digits.iter().rev().map(|d| digit_to_char(*d).unwrap()));
+    Ok(output)
+}
+
+/// Parses `val` in `base` into the big-endian bytes of its value, the inverse of
+/// `bytes_to_base`, eg. `1ekf` in base 36 -> `[255, 255]`
+///
+/// Each leading `0` digit becomes a leading zero byte, and an empty string an empty buffer.
+pub fn base_to_bytes(val: &str, base: usize) -> Result<Vec<u8>, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+
+    let zeros: usize = val.chars().take_while(|c| *c == '0').count();
+    // bytes of the value so far, least significant first
+    let mut bytes: Vec<u8> = Vec::new();
+    for (i, c) in val.chars().enumerate().skip(zeros) {
+        mul_add(&mut bytes, base, digit_value(c, i, base)? as usize);
+    }
+
+    let mut output: Vec<u8> = vec![0; zeros];
+    output.extend(bytes.iter().rev());
+    Ok(output)
+}
+
+/// The order `hex_byte_pairs` writes bytes in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// Most significant byte first, the order the digits are written in
+    #[default]
+    Big,
+    /// Least significant byte first, the order of most machines' memory
+    Little,
+}
+
+/// Splits the hex digits `hex` into space separated bytes in `order`, with a leading `0`
+/// for an odd number of digits, eg. `11223344` little-endian -> `44 33 22 11`
+///
+/// The digits keep their letter case; a sign or radix point is not part of any byte.
+pub fn hex_byte_pairs(hex: &str, order: ByteOrder) -> Result<String, ConvertError> {
+    if hex.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+    if hex.starts_with('-') {
+        return Err(ConvertError::Negative(hex.to_string()));
+    }
+    if let Some((index, ch)) = hex
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !c.is_ascii_hexdigit())
+    {
+        return Err(ConvertError::InvalidDigit {
+            ch,
+            index,
+            base: 16,
+        });
+    }
+
+    let padded: String = match hex.len() % 2 {
+        1 => format!("0{}", hex),
+        _ => hex.to_string(),
+    };
+    // the digits are ascii, so every two bytes of the string are one byte of the value
+    let mut pairs: Vec<&str> = (0..padded.len())
+        .step_by(2)
+        .map(|i| &padded[i..i + 2])
+        .collect();
+    if order == ByteOrder::Little {
+        pairs.reverse();
+    }
+    Ok(pairs.join(" "))
+}
+
+/// Writes each UTF-8 byte of `text` in `base`, padded to as many digits as 255 takes,
+/// eg. `Hi` in base 16 -> `4869` and `é` -> `c3a9`
+pub fn text_to_base(text: &str, base: usize) -> Result<String, ConvertError> {
+    let width: usize = digit_count(u8::MAX.into(), base)?;
+    text.bytes()
+        .map(|b| dec_to_base(b.into(), base).map(|d| pad_digits(&d, width)))
+        .collect()
+}
+
+/// Reads `val` in `base` as fixed width bytes of UTF-8 text, the inverse of `text_to_base`,
+/// eg. `4869` in base 16 -> `Hi`
+///
+/// Each byte takes as many digits as 255 does in `base`, so the digits must come in whole
+/// bytes, each at most 255, and together spell valid UTF-8.
+pub fn base_to_text(val: &str, base: usize) -> Result<String, ConvertError> {
+    let width: usize = digit_count(u8::MAX.into(), base)?;
+    let chars: Vec<char> = val.chars().collect();
+    if !chars.len().is_multiple_of(width) {
+        return Err(ConvertError::WidthMismatch {
+            input: val.to_string(),
+            expected: chars.len().next_multiple_of(width),
+            found: chars.len(),
+        });
+    }
+
+    let mut bytes: Vec<u8> = Vec::with_capacity(chars.len() / width);
+    for (i, chunk) in chars.chunks(width).enumerate() {
+        // each chunk is only digits, so a `0x` or `0d` in it is not a prefix
+        let mut byte: Vec<u8> = Vec::new();
+        for (j, c) in chunk.iter().enumerate() {
+            mul_add(
+                &mut byte,
+                base,
+                digit_value(*c, i * width + j, base)? as usize,
+            );
+        }
+        match byte[..] {
+            [] => bytes.push(0),
+            [b] => bytes.push(b),
+            _ => {
+                return Err(ConvertError::Overflow {
+                    input: chunk.iter().collect(),
+                    base,
+                })
+            }
+        }
+    }
+    String::from_utf8(bytes).map_err(|e| ConvertError::InvalidUtf8 {
+        index: e.utf8_error().valid_up_to(),
+    })
+}
+
+// one step of Horner's method on little-endian bytes: bytes = bytes * base + digit
+pub(crate) fn mul_add(bytes: &mut Vec<u8>, base: usize, digit: usize) {
+    let mut carry: usize = digit;
+    for b in bytes.iter_mut() {
+        carry += usize::from(*b) * base;
+        *b = (carry & 0xff) as u8;
+        carry >>= 8;
+    }
+    while carry > 0 {
+        bytes.push((carry & 0xff) as u8);
+        carry >>= 8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_to_base_test() {
+        let vals: Vec<(Vec<u8>, usize, &str)> = vec![
+            (vec![255, 255], 36, "1ekf"),
+            (vec![1, 0], 36, "74"),
+            (vec![1, 0], 16, "100"),
+            (vec![0xde, 0xad, 0xbe, 0xef], 16, "deadbeef"),
+            (vec![0, 0, 1], 2, "001"),
+            (vec![0], 36, "0"),
+            (vec![], 36, ""),
+        ];
+        for v in vals {
+            assert_eq!(bytes_to_base(&v.0, v.1), Ok(v.2.to_string()));
+            assert_eq!(base_to_bytes(v.2, v.1), Ok(v.0));
+        }
+        assert_eq!(bytes_to_base(&[1], 63), Err(ConvertError::InvalidBase(63)));
+        assert_eq!(
+            base_to_bytes("0z!", 36),
+            Err(ConvertError::InvalidDigit {
+                ch: '!',
+                index: 2,
+                base: 36
+            })
+        );
+    }
+
+    #[test]
+    fn hex_byte_pairs_test() {
+        let vals: Vec<(&str, &str, &str)> = vec![
+            ("11223344", "11 22 33 44", "44 33 22 11"),
+            ("deadBEEF", "de ad BE EF", "EF BE ad de"),
+            ("abc", "0a bc", "bc 0a"),
+            ("7", "07", "07"),
+            ("0000ff", "00 00 ff", "ff 00 00"),
+        ];
+        for v in vals {
+            assert_eq!(hex_byte_pairs(v.0, ByteOrder::Big), Ok(v.1.to_string()));
+            assert_eq!(hex_byte_pairs(v.0, ByteOrder::Little), Ok(v.2.to_string()));
+        }
+        assert_eq!(
+            hex_byte_pairs("12.8", ByteOrder::Big),
+            Err(ConvertError::InvalidDigit {
+                ch: '.',
+                index: 2,
+                base: 16
+            })
+        );
+        assert_eq!(
+            hex_byte_pairs("-ff", ByteOrder::Little),
+            Err(ConvertError::Negative("-ff".to_string()))
+        );
+        assert_eq!(hex_byte_pairs("", ByteOrder::Big), Err(ConvertError::Empty));
+    }
+
+    #[test]
+    fn text_to_base_test() {
+        let vals: Vec<(&str, usize, &str)> = vec![
+            ("Hi", 16, "4869"),
+            ("Hi", 2, "0100100001101001"),
+            ("Hi", 10, "072105"),
+            ("Hi", 36, "20 2x"),
+            ("é", 16, "c3a9"),
+            ("", 16, ""),
+        ];
+        for v in vals {
+            let digits: String = v.2.replace(' ', "");
+            assert_eq!(text_to_base(v.0, v.1), Ok(digits.clone()));
+            assert_eq!(base_to_text(&digits, v.1), Ok(v.0.to_string()));
+        }
+        assert_eq!(base_to_text("4869", 16), Ok("Hi".to_string()));
+        assert_eq!(base_to_text("4F4B", 16), Ok("OK".to_string()));
+
+        assert_eq!(
+            base_to_text("486", 16),
+            Err(ConvertError::WidthMismatch {
+                input: "486".to_string(),
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            base_to_text("072256", 10),
+            Err(ConvertError::Overflow {
+                input: "256".to_string(),
+                base: 10
+            })
+        );
+        assert_eq!(
+            base_to_text("48g9", 16),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 2,
+                base: 16
+            })
+        );
+        // a chunk that starts like a prefix is still only digits
+        assert_eq!(
+            base_to_text("0x48", 16),
+            Err(ConvertError::InvalidDigit {
+                ch: 'x',
+                index: 1,
+                base: 16
+            })
+        );
+        assert_eq!(
+            base_to_text("0d1072", 10),
+            Err(ConvertError::InvalidDigit {
+                ch: 'd',
+                index: 1,
+                base: 10
+            })
+        );
+        // a lone continuation byte is not UTF-8
+        assert_eq!(
+            base_to_text("48a9", 16),
+            Err(ConvertError::InvalidUtf8 { index: 1 })
+        );
+        assert_eq!(text_to_base("Hi", 63), Err(ConvertError::InvalidBase(63)));
+    }
+
+    #[test]
+    fn bytes_long_value_test() {
+        // a 4000 bit value, far past what a u128 holds
+        let bytes: Vec<u8> = vec![0xff; 500];
+        assert_eq!(bytes_to_base(&bytes, 2), Ok("1".repeat(4000)));
+        assert_eq!(bytes_to_base(&bytes, 16), Ok("f".repeat(1000)));
+        assert_eq!(base_to_bytes(&"1".repeat(4000), 2), Ok(bytes));
+    }
+
+    #[test]
+    fn bytes_round_trip_test() {
+        let vals: Vec<Vec<u8>> = vec![
+            b"hello world".to_vec(),
+            vec![0, 0, 255, 0, 1],
+            (0..=255).collect(),
+            vec![0xff; 40],
+        ];
+        for v in vals {
+            for base in [2, 36, 62] {
+                let encoded: String = bytes_to_base(&v, base).unwrap();
+                assert_eq!(base_to_bytes(&encoded, base), Ok(v.clone()));
+            }
+        }
+        // the value matches the integer conversion for buffers that fit in a u128
+        let value: u128 = u128::from_be_bytes(*b"0123456789abcdef");
+        assert_eq!(
+            bytes_to_base(b"0123456789abcdef", 36),
+            crate::dec_to_base(value, 36)
+        );
+    }
+}
@@ -0,0 +1,152 @@
+/*
+    A bounded least-recently-used cache of conversions, for batches where values repeat
+    Entries are keyed on the value and both bases. Each use stamps the entry, and the
+    queue of stamps is read oldest first to find the entry to evict
+*/
+
+use crate::{apply_case, convert_value, ConvertError, OutputCase};
+use std::collections::{HashMap, VecDeque};
+
+type Key = (String, usize, usize);
+
+/// Memoizes `convert_value`, keeping the `capacity` most recently used results
+///
+/// Only successful conversions are kept; a value that fails is converted again each time.
+#[derive(Debug)]
+pub struct ConvertCache {
+    capacity: usize,
+    // each result with the stamp of its last use
+    entries: HashMap<Key, (String, u64)>,
+    // keys in the order they were used, a key is stale once its entry has a newer stamp
+    order: VecDeque<(Key, u64)>,
+    stamp: u64,
+    hits: usize,
+    misses: usize,
+}
+
+impl ConvertCache {
+    /// Creates an empty cache holding at most `capacity` results, at least 1
+    pub fn new(capacity: usize) -> Self {
+        ConvertCache {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            stamp: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Converts `val` like `convert_value`, reusing the result of an earlier identical call
+    pub fn convert(&mut self, bases: (usize, usize), val: &str) -> Result<String, ConvertError> {
+        let key: Key = (val.to_string(), bases.0, bases.1);
+        self.stamp += 1;
+        if let Some((result, stamp)) = self.entries.get_mut(&key) {
+            *stamp = self.stamp;
+            let result: String = result.clone();
+            self.hits += 1;
+            self.order.push_back((key, self.stamp));
+            self.compact();
+            return Ok(result);
+        }
+
+        self.misses += 1;
+        let result: String = convert_value(bases, val)?;
+        if self.entries.len() == self.capacity {
+            self.evict();
+        }
+        self.entries
+            .insert(key.clone(), (result.clone(), self.stamp));
+        self.order.push_back((key, self.stamp));
+        Ok(result)
+    }
+
+    /// Like `convert`, but in the letter case of `case`, see `convert_value_cased`
+    pub fn convert_cased(
+        &mut self,
+        bases: (usize, usize),
+        val: &str,
+        case: OutputCase,
+    ) -> Result<String, ConvertError> {
+        Ok(apply_case(self.convert(bases, val)?, bases.1, case))
+    }
+
+    /// How many conversions were answered from the cache
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// How many conversions had to be computed
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// How many results the cache holds
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache holds no results
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // drop the least recently used entry, skipping stale queue slots
+    fn evict(&mut self) {
+        while let Some((key, stamp)) = self.order.pop_front() {
+            if self.entries.get(&key).is_some_and(|(_, s)| *s == stamp) {
+                self.entries.remove(&key);
+                return;
+            }
+        }
+    }
+
+    // keep repeated hits from growing the queue past twice the capacity
+    fn compact(&mut self) {
+        if self.order.len() > 2 * self.capacity {
+            let entries = &self.entries;
+            self.order
+                .retain(|(key, stamp)| entries.get(key).is_some_and(|(_, s)| s == stamp));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_cache_test() {
+        let mut cache = ConvertCache::new(2);
+        // decimal values to a base, with the hit and miss counts after each conversion
+        let vals: Vec<(usize, &str, (usize, usize))> = vec![
+            (16, "255", (0, 1)),
+            (16, "255", (1, 1)),
+            (2, "255", (1, 2)),
+            (16, "255", (2, 2)),
+            // a third key evicts the least recently used, 255 to base 2
+            (8, "8", (2, 3)),
+            (16, "255", (3, 3)),
+            (2, "255", (3, 4)),
+        ];
+        for v in vals {
+            assert_eq!(cache.convert((10, v.0), v.1), convert_value((10, v.0), v.1));
+            assert_eq!((cache.hits(), cache.misses()), v.2);
+            assert!(cache.len() <= 2);
+        }
+
+        // errors are returned but not kept
+        assert_eq!(cache.convert((10, 16), "fg"), convert_value((10, 16), "fg"));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(
+            cache.convert_cased((10, 16), "255", OutputCase::Upper),
+            Ok("FF".to_string())
+        );
+
+        // many hits on one key do not grow the queue without bound
+        for _ in 0..100 {
+            cache.convert((10, 16), "255").unwrap();
+        }
+        assert!(cache.order.len() <= 2 * cache.capacity + 1);
+    }
+}
@@ -0,0 +1,148 @@
+/*
+    The plain `<inbase> <outbase> <value>...` conversion of the command line as a function
+    It returns each value's output or error instead of printing and exiting, so the binary's
+    behavior can be tested in-process, eg. from the integration tests in tests/
+*/
+
+use crate::{convert_negabase, convert_value_cased, ConvertError, OutputCase, MAX_BASE};
+
+/// Reads a base arg, either a number or one of the names bin, oct, dec and hex
+pub fn parse_base_arg(s: &str) -> Result<isize, ConvertError> {
+    match s {
+        "bin" => Ok(2),
+        "oct" => Ok(8),
+        "dec" => Ok(10),
+        "hex" => Ok(16),
+        _ => s
+            .parse::<isize>()
+            .map_err(|_| ConvertError::InvalidBaseArg(s.to_string())),
+    }
+}
+
+/// Converts `val` between the bases of a command line, in negabase form when either is negative
+pub fn convert_bases(
+    bases: (isize, isize),
+    val: &str,
+    case: OutputCase,
+) -> Result<String, ConvertError> {
+    match bases {
+        (0.., 0..) => convert_value_cased((bases.0 as usize, bases.1 as usize), val, case),
+        _ => convert_negabase(bases, val),
+    }
+}
+
+/// Reads the `<inbase> <outbase>` args, checking that each is a supported base: 1 to
+/// MAX_BASE, -2 to -MAX_BASE, or an input base of 0 to detect it from each value
+pub fn parse_bases(from: &str, to: &str) -> Result<(isize, isize), ConvertError> {
+    let bases: (isize, isize) = (parse_base_arg(from)?, parse_base_arg(to)?);
+    // base 1 is unary, which has no negative form
+    for (i, b) in [bases.0, bases.1].into_iter().enumerate() {
+        let valid: bool =
+            (i == 0 && b == 0) || b == 1 || (2..=MAX_BASE).contains(&b.unsigned_abs());
+        match (valid, b) {
+            (true, _) => {}
+            (false, ..=-1) => return Err(ConvertError::InvalidNegativeBase(b)),
+            (false, _) => return Err(ConvertError::InvalidBase(b as usize)),
+        }
+    }
+    Ok(bases)
+}
+
+/// Converts each value of `<inbase> <outbase> <value>...` args, one result per value,
+/// eg. `10 16 255 fg` -> `[Ok("ff"), Err(InvalidDigit ..)]`
+///
+/// A bad value does not stop the others, but bad args fail the whole run.
+/// Negative bases convert to and from negabase form, as with `convert_negabase`.
+pub fn run(args: Vec<String>) -> Result<Vec<Result<String, ConvertError>>, ConvertError> {
+    run_with(args, |bases, v| convert_bases(bases, v, OutputCase::Lower))
+}
+
+/// Like `run`, but converting each value with `convert`, given the parsed bases,
+/// eg. to apply the formatting flags of the command line to each result
+pub fn run_with<F>(
+    args: Vec<String>,
+    mut convert: F,
+) -> Result<Vec<Result<String, ConvertError>>, ConvertError>
+where
+    F: FnMut((isize, isize), &str) -> Result<String, ConvertError>,
+{
+    if args.len() < 3 {
+        return Err(ConvertError::MissingArgs(args.len()));
+    }
+    let bases: (isize, isize) = parse_bases(&args[0], &args[1])?;
+    Ok(args[2..].iter().map(|v| convert(bases, v)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_base_arg_test() {
+        let vals: Vec<(&str, isize)> = vec![
+            ("bin", 2),
+            ("oct", 8),
+            ("dec", 10),
+            ("hex", 16),
+            ("36", 36),
+            ("-2", -2),
+        ];
+        for v in vals {
+            assert_eq!(parse_base_arg(v.0), Ok(v.1));
+        }
+        assert_eq!(
+            parse_base_arg("Hex"),
+            Err(ConvertError::InvalidBaseArg("Hex".to_string()))
+        );
+    }
+
+    #[test]
+    fn run_test() {
+        let vals: Vec<(&str, Vec<&str>)> = vec![
+            ("10 16 255", vec!["ff"]),
+            ("dec hex 255 16", vec!["ff", "10"]),
+            ("0 10 0x1f 0b11", vec!["31", "3"]),
+            ("10 -2 6", vec!["11010"]),
+            ("16 2 -a", vec!["-1010"]),
+        ];
+        for v in vals {
+            let expected: Vec<Result<String, ConvertError>> =
+                v.1.iter().map(|o| Ok(o.to_string())).collect();
+            assert_eq!(run(args(v.0)), Ok(expected));
+        }
+
+        assert_eq!(run(args("10 16")), Err(ConvertError::MissingArgs(2)));
+        assert_eq!(
+            run(args("ten 16 255")),
+            Err(ConvertError::InvalidBaseArg("ten".to_string()))
+        );
+        assert_eq!(run(args("10 63 255")), Err(ConvertError::InvalidBase(63)));
+        assert_eq!(run(args("10 0 255")), Err(ConvertError::InvalidBase(0)));
+        assert_eq!(
+            run(args("10 -1 255")),
+            Err(ConvertError::InvalidNegativeBase(-1))
+        );
+        // each value goes through the given conversion
+        assert_eq!(
+            run_with(args("hex 10 ff 10"), |bases, v| Ok(format!("{:?} {}", bases, v))),
+            Ok(vec![Ok("(16, 10) ff".to_string()), Ok("(16, 10) 10".to_string())])
+        );
+        // a failing value keeps the results on either side of it
+        assert_eq!(
+            run(args("16 10 ff fg 10")),
+            Ok(vec![
+                Ok("255".to_string()),
+                Err(ConvertError::InvalidDigit {
+                    ch: 'g',
+                    index: 1,
+                    base: 16
+                }),
+                Ok("16".to_string()),
+            ])
+        );
+    }
+}
@@ -0,0 +1,148 @@
+/*
+    Worked explanations of a conversion, for teaching
+    Base to decimal expands each digit by its place value,
+    decimal to base shows the repeated division by the base
+*/
+
+use crate::{
+    base_to_dec, digit_to_char, digit_value, strip_base_prefix, ConvertError, MAX_BASE,
+};
+
+/// Explains parsing `val` in `base` as a sum of place values,
+/// eg. `ff` in base 16 -> `f*16^1 + f*16^0 = 15*16 + 15*1 = 255`
+///
+/// A zero digit adds nothing, so its product is written as a bare `0`, eg. `101` in base 2
+/// -> `1*2^2 + 0*2^1 + 1*2^0 = 1*4 + 0 + 1*1 = 5`.
+pub fn explain_base_to_dec(val: &str, base: usize) -> Result<String, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    // the value and any error in it come from `base_to_dec`, so the steps below only
+    // describe digits it has already read
+    let out_val: u128 = base_to_dec(val, base)?;
+
+    let in_str: &str = strip_base_prefix(val, base);
+    let prefix_len: usize = val.len() - in_str.len();
+    let digit_count: usize = in_str.chars().count();
+
+    let mut powers: Vec<String> = Vec::new();
+    let mut products: Vec<String> = Vec::new();
+    for (i, c) in in_str.chars().enumerate() {
+        let char_val: u128 = digit_value(c, prefix_len + i, base)?;
+        let exp: usize = digit_count - 1 - i;
+        powers.push(format!("{}*{}^{}", c, base, exp));
+        // a nonzero digit's place value is at most the value itself, so it fits in a
+        // u128, while a leading zero's may not
+        products.push(match char_val {
+            0 => String::from("0"),
+            v => format!("{}*{}", v, (base as u128).pow(exp as u32)),
+        });
+    }
+    Ok(format!(
+        "{} = {} = {}",
+        powers.join(" + "),
+        products.join(" + "),
+        out_val
+    ))
+}
+
+/// Explains rendering `n` in `base` by repeated division, one step per line;
+/// the remainders read from the last step up give the digits, eg. 255 in base 16:
+///
+/// ```text
+/// 255 / 16 = 15 r 15 (f)
+/// 15 / 16 = 0 r 15 (f)
+/// 255 = ff
+/// ```
+pub fn explain_dec_to_base(n: u128, base: usize) -> Result<String, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+
+    let mut steps: Vec<String> = Vec::new();
+    let mut digits = String::new();
+    let mut cur_val: u128 = n;
+    loop {
+        let (quot, rem) = (cur_val / base as u128, cur_val % base as u128);
+        // rem is below base, so it always maps to a digit
+        let digit: char = digit_to_char(rem as u8).unwrap();
+        steps.push(format!(
+            "{} / {} = {} r {} ({})",
+            cur_val, base, quot, rem, digit
+        ));
+        digits.insert(0, digit);
+        cur_val = quot;
+        if cur_val == 0 {
+            break;
+        }
+    }
+    steps.push(format!("{} = {}", n, digits));
+    Ok(steps.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explain_base_to_dec_test() {
+        let vals: Vec<(&str, usize, &str)> = vec![
+            ("ff", 16, "f*16^1 + f*16^0 = 15*16 + 15*1 = 255"),
+            ("101", 2, "1*2^2 + 0*2^1 + 1*2^0 = 1*4 + 0 + 1*1 = 5"),
+            ("0x1A", 16, "1*16^1 + A*16^0 = 1*16 + 10*1 = 26"),
+            ("7", 10, "7*10^0 = 7*1 = 7"),
+        ];
+        for v in vals {
+            assert_eq!(explain_base_to_dec(v.0, v.1), Ok(v.2.to_string()));
+        }
+        // leading zeros past the largest u128 place value still explain
+        let padded: String = "0".repeat(40) + "1";
+        let explained: String = explain_base_to_dec(&padded, 16).unwrap();
+        assert!(explained.starts_with("0*16^40 + "));
+        assert!(explained.ends_with(&format!(" = {}1*1 = 1", "0 + ".repeat(40))));
+        assert_eq!(
+            explain_base_to_dec(&"f".repeat(33), 16),
+            Err(ConvertError::Overflow {
+                input: "f".repeat(33),
+                base: 16
+            })
+        );
+        assert_eq!(
+            explain_base_to_dec("fg", 16),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 1,
+                base: 16
+            })
+        );
+        assert_eq!(explain_base_to_dec("", 16), Err(ConvertError::Empty));
+        assert_eq!(
+            explain_base_to_dec("1", 1),
+            Err(ConvertError::InvalidBase(1))
+        );
+    }
+
+    #[test]
+    fn explain_dec_to_base_test() {
+        let vals: Vec<(u128, usize, &str)> = vec![
+            (
+                255,
+                16,
+                "255 / 16 = 15 r 15 (f)\n15 / 16 = 0 r 15 (f)\n255 = ff",
+            ),
+            (
+                5,
+                2,
+                "5 / 2 = 2 r 1 (1)\n2 / 2 = 1 r 0 (0)\n1 / 2 = 0 r 1 (1)\n5 = 101",
+            ),
+            (0, 8, "0 / 8 = 0 r 0 (0)\n0 = 0"),
+        ];
+        for v in vals {
+            assert_eq!(explain_dec_to_base(v.0, v.1), Ok(v.2.to_string()));
+        }
+        assert_eq!(
+            explain_dec_to_base(1, 63),
+            Err(ConvertError::InvalidBase(63))
+        );
+    }
+}
@@ -0,0 +1,63 @@
+/*
+    Prime factorization by trial division, eg. 255 -> 3 * 5 * 17
+    Dividing out each factor as it is found keeps the search to the square root of what
+    is left, so the time grows with the second largest prime factor of the value
+*/
+
+/// Returns the prime factors of `n` in ascending order, each repeated by its multiplicity,
+/// eg. 360 -> [2, 2, 2, 3, 3, 5]; 0 and 1 have no prime factors
+pub fn prime_factors(n: u128) -> Vec<u128> {
+    let mut factors: Vec<u128> = Vec::new();
+    if n < 2 {
+        return factors;
+    }
+
+    let mut cur_val: u128 = n;
+    let mut divisor: u128 = 2;
+    // divisor * divisor can not overflow while it is at most cur_val, below 2^128
+    while divisor <= cur_val / divisor {
+        while cur_val.is_multiple_of(divisor) {
+            factors.push(divisor);
+            cur_val /= divisor;
+        }
+        // after 2, only odd divisors can be prime
+        divisor += if divisor == 2 { 1 } else { 2 };
+    }
+    if cur_val > 1 {
+        factors.push(cur_val);
+    }
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prime_factors_test() {
+        let vals: Vec<(u128, Vec<u128>)> = vec![
+            (0, vec![]),
+            (1, vec![]),
+            (2, vec![2]),
+            (17, vec![17]),
+            (255, vec![3, 5, 17]),
+            (360, vec![2, 2, 2, 3, 3, 5]),
+            (1024, vec![2; 10]),
+            (65537, vec![65537]),
+            (4294967297, vec![641, 6700417]),
+            (u64::MAX as u128, vec![3, 5, 17, 257, 641, 65537, 6700417]),
+        ];
+        for v in vals {
+            assert_eq!(prime_factors(v.0), v.1);
+        }
+
+        // the factors multiply back to the value, and each one is prime
+        for n in 2..3000u128 {
+            let factors: Vec<u128> = prime_factors(n);
+            assert_eq!(factors.iter().product::<u128>(), n);
+            for f in factors {
+                assert_eq!(prime_factors(f), vec![f]);
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+/*
+    The factorial number system, where the digit i places from the right has place value i!
+    and runs 0..=i, eg. 463 = 3*5! + 4*4! + 1*3! + 0*2! + 1*1! + 0*0! -> 341010
+    Digits past 9 use the usual letters, so every u128 fits in 35 digits
+*/
+
+use crate::{digit_to_char, digit_value, ConvertError};
+
+/// Renders `n` in the factorial number system, eg. 463 -> `341010`
+pub fn dec_to_factoradic(n: u128) -> String {
+    // the digit at place i is the remainder of dividing by i + 1, least significant first
+    let mut digits: Vec<char> = vec!['0'];
+    let mut cur_val: u128 = n;
+    let mut radix: u128 = 2;
+    while cur_val > 0 {
+        // a digit is at most 34 for a u128, so it always maps to a char
+        digits.push(digit_to_char((cur_val % radix) as u8).unwrap());
+        cur_val /= radix;
+        radix += 1;
+    }
+    digits.iter().rev().collect()
+}
+
+/// Parses the factoradic literal `s` into its value, eg. `341010` -> 463
+///
+/// The digit at place i must be at most i, so an `InvalidDigit` reports i + 1 as its base.
+pub fn factoradic_to_dec(s: &str) -> Result<u128, ConvertError> {
+    if s.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+
+    let len: usize = s.chars().count();
+    let mut out_val: u128 = 0;
+    for (i, c) in s.chars().enumerate() {
+        let place: usize = len - 1 - i;
+        let char_val: u128 = digit_value(c, i, place + 1)?;
+        out_val = out_val
+            .checked_mul(place as u128 + 1)
+            .and_then(|v| v.checked_add(char_val))
+            // the radix of the leftmost place stands in for the base
+            .ok_or_else(|| ConvertError::Overflow {
+                input: s.to_string(),
+                base: len,
+            })?;
+    }
+    Ok(out_val)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factoradic_test() {
+        let vals: Vec<(u128, &str)> = vec![
+            (0, "0"),
+            (1, "10"),
+            (2, "100"),
+            (5, "210"),
+            (6, "1000"),
+            (463, "341010"),
+            (859, "1103010"),
+            (3628799, "9876543210"),
+            (3628800, "10000000000"),
+        ];
+        for v in vals {
+            assert_eq!(dec_to_factoradic(v.0), v.1);
+            assert_eq!(factoradic_to_dec(v.1), Ok(v.0));
+        }
+
+        // every value survives the round trip, up to the largest u128
+        for n in (0..5000u128).chain([u128::MAX - 1, u128::MAX]) {
+            assert_eq!(factoradic_to_dec(&dec_to_factoradic(n)), Ok(n));
+        }
+        assert_eq!(dec_to_factoradic(u128::MAX).len(), 35);
+
+        assert_eq!(
+            factoradic_to_dec("201"),
+            Err(ConvertError::InvalidDigit {
+                ch: '1',
+                index: 2,
+                base: 1
+            })
+        );
+        assert_eq!(
+            factoradic_to_dec("30"),
+            Err(ConvertError::InvalidDigit {
+                ch: '3',
+                index: 0,
+                base: 2
+            })
+        );
+        assert_eq!(factoradic_to_dec(""), Err(ConvertError::Empty));
+        assert!(matches!(
+            factoradic_to_dec(&format!("1{}", "0".repeat(35))),
+            Err(ConvertError::Overflow { .. })
+        ));
+    }
+}
@@ -0,0 +1,102 @@
+/*
+    IEEE-754 float bit patterns
+    A float is rendered as the integer value of its bits, eg. 1.0 -> 3ff0000000000000 in base 16
+*/
+
+use crate::{base_to_dec, dec_to_base, pad_digits, ConvertError};
+
+/// Width of the IEEE-754 format used for a float's bits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatWidth {
+    /// 32-bit single precision, `f32`
+    F32,
+    /// 64-bit double precision, `f64`
+    #[default]
+    F64,
+}
+
+impl FloatWidth {
+    // the largest bit pattern of the width, used to size the output
+    fn max_bits(self) -> u128 {
+        match self {
+            FloatWidth::F32 => u32::MAX.into(),
+            FloatWidth::F64 => u64::MAX.into(),
+        }
+    }
+}
+
+/// Renders the bits of `f` at `width` in `base`, zero-padded to the full width,
+/// eg. 1.0 as F64 in base 16 -> `3ff0000000000000`
+///
+/// F32 first rounds `f` to the nearest `f32`.
+pub fn float_to_bits(f: f64, base: usize, width: FloatWidth) -> Result<String, ConvertError> {
+    let bits: u128 = match width {
+        FloatWidth::F32 => (f as f32).to_bits().into(),
+        FloatWidth::F64 => f.to_bits().into(),
+    };
+    let digits: usize = dec_to_base(width.max_bits(), base)?.len();
+    Ok(pad_digits(&dec_to_base(bits, base)?, digits))
+}
+
+/// Reads `val` in `base` as the bits of a float at `width`, eg. `3f800000` as F32 -> 1.0
+pub fn bits_to_float(val: &str, base: usize, width: FloatWidth) -> Result<f64, ConvertError> {
+    let bits: u128 = base_to_dec(val, base)?;
+    if bits > width.max_bits() {
+        return Err(ConvertError::Overflow {
+            input: val.to_string(),
+            base,
+        });
+    }
+    match width {
+        FloatWidth::F32 => Ok(f32::from_bits(bits as u32).into()),
+        FloatWidth::F64 => Ok(f64::from_bits(bits as u64)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn float_to_bits_test() {
+        let vals: Vec<(f64, usize, FloatWidth, &str)> = vec![
+            (1.0, 16, FloatWidth::F64, "3ff0000000000000"),
+            (-2.0, 16, FloatWidth::F64, "c000000000000000"),
+            (0.0, 16, FloatWidth::F64, "0000000000000000"),
+            (1.0, 16, FloatWidth::F32, "3f800000"),
+            (0.5, 2, FloatWidth::F32, "00111111000000000000000000000000"),
+            (f64::INFINITY, 16, FloatWidth::F64, "7ff0000000000000"),
+        ];
+        for v in vals {
+            assert_eq!(float_to_bits(v.0, v.1, v.2), Ok(v.3.to_string()));
+        }
+    }
+
+    #[test]
+    fn bits_to_float_test() {
+        let vals: Vec<(&str, usize, FloatWidth, f64)> = vec![
+            ("3ff0000000000000", 16, FloatWidth::F64, 1.0),
+            ("3f800000", 16, FloatWidth::F32, 1.0),
+            ("40490fdb", 16, FloatWidth::F32, std::f32::consts::PI as f64),
+        ];
+        for v in vals {
+            assert_eq!(bits_to_float(v.0, v.1, v.2), Ok(v.3));
+        }
+        assert_eq!(
+            bits_to_float("100000000", 16, FloatWidth::F32),
+            Err(ConvertError::Overflow {
+                input: "100000000".to_string(),
+                base: 16
+            })
+        );
+
+        // every value survives the trip through its bits
+        for f in [2.5, -0.1, 1e300, f64::MIN_POSITIVE, -0.0] {
+            for base in [2, 16, 36] {
+                let bits: String = float_to_bits(f, base, FloatWidth::F64).unwrap();
+                let back: f64 = bits_to_float(&bits, base, FloatWidth::F64).unwrap();
+                assert_eq!(back.to_bits(), f.to_bits());
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+/*
+    Reflected binary (Gray) codes, where consecutive values differ in a single bit
+    Each bit of the code is the XOR of a bit of the value and the bit to its left,
+    eg. 5 = 101 -> 111
+*/
+
+use crate::{base_to_dec, dec_to_base, ConvertError};
+
+/// Renders the Gray code of `n` in binary, eg. 5 -> `111`
+pub fn to_gray(n: u128) -> String {
+    // base 2 is always valid
+    dec_to_base(n ^ (n >> 1), 2).unwrap()
+}
+
+/// Parses the binary Gray code `s` back into its value, eg. `111` -> 5
+pub fn from_gray(s: &str) -> Result<u128, ConvertError> {
+    let mut n: u128 = base_to_dec(s, 2)?;
+    // each bit of the value is the XOR of every code bit from it leftward
+    let mut shift: u32 = 1;
+    while shift < u128::BITS {
+        n ^= n >> shift;
+        shift *= 2;
+    }
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gray_test() {
+        let vals: Vec<(u128, &str)> = vec![
+            (0, "0"),
+            (1, "1"),
+            (2, "11"),
+            (3, "10"),
+            (4, "110"),
+            (5, "111"),
+            (7, "100"),
+            (8, "1100"),
+            (255, "10000000"),
+        ];
+        for v in vals {
+            assert_eq!(to_gray(v.0), v.1);
+            assert_eq!(from_gray(v.1), Ok(v.0));
+        }
+
+        // consecutive codes differ in exactly one bit, and every code reads back
+        for n in 0..1000u128 {
+            let (a, b) = (from_gray(&to_gray(n)), from_gray(&to_gray(n + 1)));
+            assert_eq!((a, b), (Ok(n), Ok(n + 1)));
+            let diff: u128 = (n ^ (n >> 1)) ^ ((n + 1) ^ ((n + 1) >> 1));
+            assert_eq!(diff.count_ones(), 1);
+        }
+        assert_eq!(from_gray(&to_gray(u128::MAX)), Ok(u128::MAX));
+        assert_eq!(
+            from_gray("102"),
+            Err(ConvertError::InvalidDigit {
+                ch: '2',
+                index: 2,
+                base: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,3238 @@
+/*
+    Conversion routines between number bases 2-62
+    Used by the `convert` binary, but usable from any crate
+    Without the default `std` feature the crate is `no_std`, leaving only the digit maps
+    and the buffer-based conversions of the `nostd` module
+*/
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "nostd"))]
+compile_error!("enable the `std` feature, or `nostd` for the no_std core alone");
+
+/// Largest supported base; digits run `0-9`, `a-z`, then `A-Z`
+pub const MAX_BASE: usize = 62;
+
+#[cfg(feature = "bignum")]
+pub mod bignum;
+#[cfg(feature = "nostd")]
+pub mod nostd;
+
+// the modules and items that need the standard library, left out of a no_std build
+macro_rules! with_std {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "std")]
+            $item
+        )*
+    };
+}
+
+with_std! {
+    use core::fmt;
+    use nostd::{parse_digits, write_digits, CoreError};
+
+    pub mod bytes;
+    pub mod cache;
+    pub mod cli;
+    pub mod explain;
+    pub mod factor;
+    pub mod factoradic;
+    pub mod float;
+    pub mod gray;
+    pub mod mixed_radix;
+    pub mod negabase;
+    pub mod number;
+    pub mod options;
+    pub mod rational;
+    pub mod roman;
+    pub mod source;
+    pub mod spell;
+    pub mod stream;
+    pub mod ternary;
+
+    pub use bytes::{
+        base_to_bytes, base_to_text, bytes_to_base, hex_byte_pairs, text_to_base, ByteOrder,
+    };
+    pub use cache::ConvertCache;
+    pub use cli::{convert_bases, parse_base_arg, parse_bases, run, run_with};
+    pub use explain::{explain_base_to_dec, explain_dec_to_base};
+    pub use factor::prime_factors;
+    pub use factoradic::{dec_to_factoradic, factoradic_to_dec};
+    pub use float::{bits_to_float, float_to_bits, FloatWidth};
+    pub use gray::{from_gray, to_gray};
+    pub use mixed_radix::{convert_nonuniform, from_mixed_radix, to_mixed_radix};
+    pub use negabase::{convert_negabase, dec_to_negabase, negabase_to_dec};
+    pub use number::{Literal, Number};
+    pub use options::ConversionOptions;
+    pub use rational::rational_to_base;
+    pub use roman::{dec_to_roman, roman_to_dec};
+    pub use source::{to_source_literal, SourceLang};
+    pub use spell::spell_digits;
+    pub use stream::BaseConverter;
+    pub use ternary::{balanced_ternary_to_dec, dec_to_balanced_ternary};
+}
+
+/// Maximum number of fractional digits emitted by `frac_to_base`
+///
+/// Fractions that do not terminate within this many digits in the output base
+/// are truncated (not rounded) toward zero.
+#[cfg(feature = "std")]
+pub const MAX_FRAC_DIGITS: usize = 32;
+
+/// Largest value `dec_to_base` writes in base 1 (unary), which takes one digit per unit
+#[cfg(feature = "std")]
+pub const MAX_UNARY: u128 = 1 << 20;
+
+/// Errors produced while converting a value between bases
+#[cfg(feature = "std")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConvertError {
+    /// `index` is the zero-based char position of `ch` in the value being parsed
+    InvalidDigit {
+        ch: char,
+        index: usize,
+        base: usize,
+    },
+    InvalidBase(usize),
+    InvalidNegativeBase(isize),
+    DuplicateSymbol(char),
+    OutOfRange {
+        value: i128,
+        bits: u32,
+    },
+    RomanRange(usize),
+    InvalidRoman(String),
+    Empty,
+    Overflow {
+        input: String,
+        base: usize,
+    },
+    ZeroDenominator,
+    /// A `0x`/`0o`/`0b` prefix naming base `prefix` on a value declared in `base`
+    PrefixBaseMismatch {
+        prefix: usize,
+        base: usize,
+    },
+    /// A value with a leading `-` where only unsigned values are accepted
+    Negative(String),
+    /// A mixed-radix `digit` at `index` that is not below its `radix`
+    MixedRadixDigit {
+        digit: usize,
+        index: usize,
+        radix: usize,
+    },
+    /// A different number of mixed-radix digits than radices
+    RadixCount {
+        digits: usize,
+        radices: usize,
+    },
+    /// A digit separator at `index` that touches the radix point, eg. `de_.ad`
+    MisplacedSeparator {
+        ch: char,
+        index: usize,
+    },
+    /// A value with `found` digits where exactly `expected` are required
+    WidthMismatch {
+        input: String,
+        expected: usize,
+        found: usize,
+    },
+    /// A source language other than `rust` or `c`, see `SourceLang`
+    UnknownLanguage(String),
+    /// An integer literal type suffix the source language does not have, eg. `u32` in C
+    InvalidSuffix(String),
+    /// Fewer than the three `<inbase> <outbase> <value>` args `run` needs
+    MissingArgs(usize),
+    /// A base arg that is neither a number nor a base name, eg. `ten`
+    InvalidBaseArg(String),
+    /// Bytes that are not valid UTF-8 text, from the byte at `index` on
+    InvalidUtf8 {
+        index: usize,
+    },
+    /// A scientific-notation value that is not a whole number, eg. `1.5e0`
+    NotWhole(String),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            // the errors the `nostd` core shares are worded the same either way, except
+            // that base 1 (unary) is only supported here
+            ConvertError::InvalidDigit { ch, index, base } => CoreError::InvalidDigit {
+                ch: *ch,
+                index: *index,
+                base: *base,
+            }
+            .fmt(f),
+            ConvertError::InvalidBase(b) => write!(
+                f,
+                "{} is not a valid base; bases: 1-{} allowed",
+                b, MAX_BASE
+            ),
+            ConvertError::InvalidNegativeBase(b) => write!(
+                f,
+                "{} is not a valid negative base; bases: -2 to -{} allowed",
+                b, MAX_BASE
+            ),
+            ConvertError::DuplicateSymbol(c) => {
+                write!(f, "`{}` appears more than once in the alphabet", c)
+            }
+            ConvertError::OutOfRange { value, bits } => {
+                write!(f, "{} does not fit in {} bits", value, bits)
+            }
+            ConvertError::RomanRange(n) => {
+                write!(f, "{} is outside the roman numeral range 1-3999", n)
+            }
+            ConvertError::InvalidRoman(s) => write!(f, "`{}` is not a valid roman numeral", s),
+            ConvertError::Empty => CoreError::Empty.fmt(f),
+            ConvertError::Overflow { input, base } => {
+                write!(f, "`{}` in base {} is too large to convert", input, base)
+            }
+            ConvertError::ZeroDenominator => write!(f, "denominator is zero"),
+            ConvertError::Negative(v) => {
+                write!(f, "`{}` is negative; only unsigned values are accepted", v)
+            }
+            ConvertError::MixedRadixDigit {
+                digit,
+                index,
+                radix,
+            } => write!(
+                f,
+                "{} at index {} is not a valid digit for radix {}",
+                digit, index, radix
+            ),
+            ConvertError::RadixCount { digits, radices } => {
+                write!(f, "{} digits were given for {} radices", digits, radices)
+            }
+            ConvertError::MisplacedSeparator { ch, index } => write!(
+                f,
+                "separator `{}` at index {} is next to the radix point",
+                ch, index
+            ),
+            ConvertError::WidthMismatch {
+                input,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{}` has {} digits, but exactly {} are required",
+                input, found, expected
+            ),
+            ConvertError::UnknownLanguage(s) => {
+                write!(f, "`{}` is not a known language; languages: rust, c", s)
+            }
+            ConvertError::InvalidSuffix(s) => {
+                write!(f, "`{}` is not a valid integer literal suffix", s)
+            }
+            ConvertError::MissingArgs(n) => write!(
+                f,
+                "expected <inbase> <outbase> <value>..., but {} args were given",
+                n
+            ),
+            ConvertError::InvalidBaseArg(s) => {
+                write!(f, "`{}` can not be parsed as a base", s)
+            }
+            ConvertError::InvalidUtf8 { index } => {
+                write!(f, "the bytes from index {} are not valid UTF-8", index)
+            }
+            ConvertError::NotWhole(s) => write!(f, "`{}` is not a whole number", s),
+            ConvertError::PrefixBaseMismatch { prefix, base } => write!(
+                f,
+                "`{}` prefix means base {}, but the value is declared as base {}",
+                base_prefix(*prefix),
+                prefix,
+                base
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConvertError {}
+
+/// Maps a digit value to its char, eg. 11 -> `b`, 37 -> `B`
+///
+/// Values past the last digit `Z` (61) have no char and give None.
+pub fn digit_to_char(i: u8) -> Option<char> {
+    match i {
+        0..=9 => Some((i + 48) as char),
+        10..=35 => Some((i + 87) as char),
+        36..=61 => Some((i + 29) as char),
+        _ => None,
+    }
+}
+
+// map a fullwidth digit or letter, U+FF10-FF19, U+FF21-FF3A or U+FF41-FF5A, to its ascii
+// form, as found in text copied from East Asian sources, eg. `１` -> `1`
+fn normalize_fullwidth(c: char) -> char {
+    match c {
+        '\u{FF10}'..='\u{FF19}' | '\u{FF21}'..='\u{FF3A}' | '\u{FF41}'..='\u{FF5A}' => {
+            // the fullwidth forms sit at a fixed offset from ascii
+            char::from_u32(u32::from(c) - 0xFEE0).unwrap_or(c)
+        }
+        _ => c,
+    }
+}
+
+/// Maps a digit char to its value, eg. `b` -> 11, `B` -> 37
+///
+/// Fullwidth digits and letters map like their ascii forms, eg. `１` -> 1.
+/// This ignores the base; see `map_char_in_base` to also reject digits outside one.
+pub fn char_to_digit(c: char) -> Option<u8> {
+    let c: char = normalize_fullwidth(c);
+    match c {
+        '0'..='9' => Some((c as u8) - 48u8),
+        'a'..='z' => Some((c as u8) - 87u8),
+        'A'..='Z' => Some((c as u8) - 29u8),
+        _ => None,
+    }
+}
+
+/// Old name of `digit_to_char`
+#[deprecated(note = "renamed to digit_to_char")]
+pub fn char_map(i: u8) -> Option<char> {
+    digit_to_char(i)
+}
+
+/// Old name of `char_to_digit`
+#[deprecated(note = "renamed to char_to_digit")]
+pub fn map_char(c: char) -> Option<u8> {
+    char_to_digit(c)
+}
+
+/// Parses `in_val` written in `base` into its integer value
+#[cfg(feature = "std")]
+pub fn base_to_dec(in_val: &str, base: usize) -> Result<u128, ConvertError> {
+    if !(1..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    // zero is the empty string in unary
+    if in_val.is_empty() && base != 1 {
+        return Err(ConvertError::Empty);
+    }
+
+    let in_str: &str = strip_base_prefix(in_val, base);
+    let prefix_len: usize = in_val.len() - in_str.len();
+    // unary digits are only counted, and a bare prefix has no digits to parse
+    if base == 1 || in_str.is_empty() {
+        for (i, c) in in_str.chars().enumerate() {
+            any_digit_value(c, prefix_len + i, base)?;
+        }
+        return Ok(in_str.chars().count() as u128);
+    }
+    parse_digits(in_str, base).map_err(|e| from_core_error(e, in_val, base, prefix_len))
+}
+
+// a `nostd` error as a ConvertError about `input`, moving a digit's index `offset` chars along
+#[cfg(feature = "std")]
+fn from_core_error(e: CoreError, input: &str, base: usize, offset: usize) -> ConvertError {
+    match e {
+        CoreError::InvalidDigit { ch, index, base } => ConvertError::InvalidDigit {
+            ch,
+            index: index + offset,
+            base,
+        },
+        CoreError::InvalidBase(b) => ConvertError::InvalidBase(b),
+        CoreError::Empty => ConvertError::Empty,
+        CoreError::Overflow | CoreError::BufferTooSmall => ConvertError::Overflow {
+            input: input.to_string(),
+            base,
+        },
+    }
+}
+
+/// Chars that may separate groups of digits in an input, eg. `1111_1111`, `ff ff` or `1'000'000`
+#[cfg(feature = "std")]
+pub const DIGIT_SEPARATORS: [char; 3] = ['_', ' ', '\''];
+
+// drop any digit separators, wherever they appear
+#[cfg(feature = "std")]
+pub(crate) fn strip_separators(val: &str) -> String {
+    val.chars()
+        .filter(|c| !DIGIT_SEPARATORS.contains(c))
+        .collect()
+}
+
+// a separator on either side of a radix point leaves it unclear which digits it groups,
+// eg. `de_.ad`; `offset` is added to the index of the separator reported
+#[cfg(feature = "std")]
+fn check_radix_separators(val: &str, offset: usize) -> Result<(), ConvertError> {
+    let chars: Vec<char> = val.chars().collect();
+    for (i, c) in chars.iter().enumerate() {
+        let next_to_point: bool = (i > 0 && chars[i - 1] == '.') || chars.get(i + 1) == Some(&'.');
+        if DIGIT_SEPARATORS.contains(c) && next_to_point {
+            return Err(ConvertError::MisplacedSeparator {
+                ch: *c,
+                index: offset + i,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that every digit of `val` is valid in `base`, eg. `ff` in base 16
+///
+/// Surrounding whitespace, a leading `-` or `+`, the base's own prefix (see `base_prefix`),
+/// radix points and separators are ignored, and so is a base 10 exponent, eg. `2.5e2`;
+/// another base's prefix is a `PrefixBaseMismatch`. Letters are case-insensitive for
+/// bases up to 36, and an `N#` prefix overrides `base`, see `convert_value`.
+#[cfg(feature = "std")]
+pub fn check_digits(val: &str, base: usize) -> Result<(), ConvertError> {
+    scan_digits(val, base, true)
+}
+
+// like `check_digits`, but without the exponent, for the functions that rewrite the digits
+// themselves rather than converting them
+#[cfg(feature = "std")]
+fn check_literal_digits(val: &str, base: usize) -> Result<(), ConvertError> {
+    scan_digits(val, base, false)
+}
+
+#[cfg(feature = "std")]
+fn scan_digits(val: &str, base: usize, exponent: bool) -> Result<(), ConvertError> {
+    let leading: usize = val.chars().take_while(char::is_ascii_whitespace).count();
+    let trimmed: &str = trim_whitespace(val);
+    check_radix_separators(trimmed, leading)?;
+    let unsigned: &str = split_sign(trimmed).1;
+    let (base, unsigned) = split_radix_prefix(unsigned)?.unwrap_or((base, unsigned));
+    check_prefix(unsigned, base)?;
+    // a well-formed exponent has only ascii digits around it; whether its value is
+    // whole is left for the conversion to report
+    let digits: &str = strip_base_prefix(unsigned, base);
+    if exponent && base == 10 && !matches!(expand_exponent(&strip_separators(digits)), Ok(None)) {
+        return Ok(());
+    }
+    // the sign and prefix are ascii, so their byte length is their char count
+    let skip: usize = trimmed.len() - digits.len();
+    for (i, c) in trimmed.chars().enumerate().skip(skip) {
+        if DIGIT_SEPARATORS.contains(&c) || c == '.' {
+            continue;
+        }
+        any_digit_value(c, leading + i, base)?;
+    }
+    Ok(())
+}
+
+// split a leading `-` or `+` from `val`, returning whether it was negative
+// a `+` is an explicit positive sign, dropped like the `-` but never output
+#[cfg(feature = "std")]
+pub(crate) fn split_sign(val: &str) -> (bool, &str) {
+    match (val.strip_prefix('-'), val.strip_prefix('+')) {
+        (Some(v), _) => (true, v),
+        (_, Some(v)) => (false, v),
+        _ => (false, val),
+    }
+}
+
+// drop surrounding whitespace, eg. the trailing newline of a piped value
+#[cfg(feature = "std")]
+pub(crate) fn trim_whitespace(val: &str) -> &str {
+    val.trim_matches(|c: char| c.is_ascii_whitespace())
+}
+
+/// Rewrites `val` in its minimal form in `base`: surrounding whitespace, the base's own
+/// prefix, separators and insignificant leading zeros are dropped, a lone `0` and a `-`
+/// sign are kept, and letters are lowercased for bases up to 36, eg. `00ff` -> `ff`,
+/// `-007` -> `-7`
+#[cfg(feature = "std")]
+pub fn canonicalize(val: &str, base: usize) -> Result<String, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    check_literal_digits(val, base)?;
+
+    let cleaned: String = strip_separators(trim_whitespace(val));
+    let (sign, use_val) = match split_sign(&cleaned) {
+        (true, v) => ("-", v),
+        (false, v) => ("", v),
+    };
+    let use_val: &str = strip_base_prefix(use_val, base);
+    if use_val.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+
+    let digits: &str = use_val.trim_start_matches('0');
+    let digits: String = match base {
+        0..=36 => digits.to_ascii_lowercase(),
+        _ => digits.to_string(),
+    };
+    match digits.chars().next() {
+        None => Ok(String::from("0")),
+        Some('.') => Ok(format!("{}0{}", sign, digits)),
+        _ => Ok(format!("{}{}", sign, digits)),
+    }
+}
+
+/// Reverses the digit order of `val` in `base`, keeping any sign and the base's own
+/// prefix in front, eg. `-0x12f` in base 16 -> `-0xf21`
+///
+/// This switches a value between most and least significant digit first, and is its
+/// own inverse.
+#[cfg(feature = "std")]
+pub fn reverse_digits(val: &str, base: usize) -> String {
+    let trimmed: &str = trim_whitespace(val);
+    let digits: &str = strip_base_prefix(split_sign(trimmed).1, base);
+    let lead: &str = &trimmed[..trimmed.len() - digits.len()];
+    lead.chars().chain(digits.chars().rev()).collect()
+}
+
+/// Largest number of places `shift_radix` moves a radix point, either way
+#[cfg(feature = "std")]
+pub const MAX_SHIFT: u32 = 4096;
+
+/// Multiplies `val` in `base` by `base^places` by moving its radix point `places`
+/// digits right (or left when negative), never converting the value,
+/// eg. `ff.0` in base 16 shifted by -1 -> `f.f`
+///
+/// The result is tidied like `canonicalize`: no prefix, separators, insignificant zeros
+/// or sign on zero, and lowercase letters for bases up to 36. A shift of more than
+/// `MAX_SHIFT` places is an `Overflow`.
+#[cfg(feature = "std")]
+pub fn shift_radix(val: &str, base: usize, places: i32) -> Result<String, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    if places.unsigned_abs() > MAX_SHIFT {
+        return Err(ConvertError::Overflow {
+            input: val.to_string(),
+            base,
+        });
+    }
+    check_literal_digits(val, base)?;
+
+    let cleaned: String = strip_separators(trim_whitespace(val));
+    let (is_neg, unsigned) = split_sign(&cleaned);
+    let unsigned: &str = strip_base_prefix(unsigned, base);
+    if unsigned.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+    let (int_part, frac_part) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    // only one radix point is allowed; report the second by its char index
+    if frac_part.contains('.') {
+        let index: usize = cleaned
+            .chars()
+            .enumerate()
+            .filter(|c| c.1 == '.')
+            .nth(1)
+            .unwrap()
+            .0;
+        return Err(ConvertError::InvalidDigit {
+            ch: '.',
+            index,
+            base,
+        });
+    }
+
+    // pad with zeros so the moved point still lands inside the digits
+    let point: i64 = int_part.len() as i64 + i64::from(places);
+    let mut digits: String = int_part.to_string() + frac_part;
+    if point < 0 {
+        digits.insert_str(0, &"0".repeat(point.unsigned_abs() as usize));
+    }
+    let point: usize = point.max(0) as usize;
+    if point > digits.len() {
+        digits.push_str(&"0".repeat(point - digits.len()));
+    }
+
+    let (int_part, frac_part) = digits.split_at(point);
+    let int_part: &str = match int_part.trim_start_matches('0') {
+        "" => "0",
+        i => i,
+    };
+    let shifted: String = match frac_part.trim_end_matches('0') {
+        "" => int_part.to_string(),
+        f => format!("{}.{}", int_part, f),
+    };
+    let shifted: String = match base {
+        0..=36 => shifted.to_ascii_lowercase(),
+        _ => shifted,
+    };
+    match is_neg && shifted != "0" {
+        true => Ok(format!("-{}", shifted)),
+        false => Ok(shifted),
+    }
+}
+
+/// Checks whether two literals, each given with its base, hold the same integer,
+/// eg. (`ff`, 16) and (`11111111`, 2); `-0` equals `0`
+#[cfg(feature = "std")]
+pub fn values_equal(a: (&str, usize), b: (&str, usize)) -> Result<bool, ConvertError> {
+    Ok(parse_signed(a.0, a.1)? == parse_signed(b.0, b.1)?)
+}
+
+// parse an integer literal with an optional `-` into its sign and magnitude; zero is never negative
+#[cfg(feature = "std")]
+pub(crate) fn parse_signed(val: &str, base: usize) -> Result<(bool, u128), ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    let cleaned: String = strip_separators(trim_whitespace(val));
+    let (is_neg, use_val) = split_sign(&cleaned);
+    let sign_len: usize = cleaned.len() - use_val.len();
+    let magnitude: u128 = base_to_dec(use_val, base).map_err(|e| shift_index(e, sign_len))?;
+    Ok((is_neg && magnitude != 0, magnitude))
+}
+
+// handle a leading 0x, 0o, 0b or 0d, stripped only when it matches base
+// elsewhere it is literal digits, eg. `0x` is 33 in base 36 and invalid in base 10
+#[cfg(feature = "std")]
+pub(crate) fn strip_base_prefix(in_val: &str, base: usize) -> &str {
+    let prefix: &str = match base {
+        10 => "0d",
+        b => base_prefix(b),
+    };
+    match prefix {
+        "" => in_val,
+        p => in_val.strip_prefix(p).unwrap_or(in_val),
+    }
+}
+
+// a 0x, 0o or 0b prefix naming a base other than `base` is a mistake rather than digits,
+// eg. `0xff` declared as base 8; a bare prefix with no digits after it is left as digits
+#[cfg(feature = "std")]
+fn check_prefix(unsigned: &str, base: usize) -> Result<(), ConvertError> {
+    for prefix in [16, 8, 2] {
+        let p: &str = base_prefix(prefix);
+        if prefix != base && unsigned.len() > p.len() && unsigned.starts_with(p) {
+            return Err(ConvertError::PrefixBaseMismatch { prefix, base });
+        }
+    }
+    Ok(())
+}
+
+// split off an Ada/Erlang style `N#` radix prefix, eg. `16#ff` -> (16, `ff`)
+// a value without a decimal `N` before its `#` has no radix prefix
+#[cfg(feature = "std")]
+pub(crate) fn split_radix_prefix(val: &str) -> Result<Option<(usize, &str)>, ConvertError> {
+    let (lead, digits) = match val.split_once('#') {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+    if lead.is_empty() || !lead.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(None);
+    }
+    match lead.parse::<usize>() {
+        Ok(b) if (2..=36).contains(&b) => Ok(Some((b, digits))),
+        b => Err(ConvertError::InvalidBase(b.unwrap_or(usize::MAX))),
+    }
+}
+
+/// Maps a digit char to its value only if it is a valid digit of `base`,
+/// eg. `g` in base 16 -> None; bases up to 36 accept either letter case
+pub fn map_char_in_base(c: char, base: usize) -> Option<u8> {
+    let folded: char = match base {
+        0..=36 => normalize_fullwidth(c).to_ascii_lowercase(),
+        _ => c,
+    };
+    char_to_digit(folded).filter(|v| usize::from(*v) < base)
+}
+
+/// Lists the bases from 2 to 36 in which every digit of `val` is valid, eg. `ff` -> 16-36
+///
+/// Surrounding whitespace and digit separators are ignored, but a sign, prefix or radix
+/// point is not a digit, so a value with one is valid in no base.
+#[cfg(feature = "std")]
+pub fn valid_bases(val: &str) -> Vec<usize> {
+    let digits: String = strip_separators(trim_whitespace(val));
+    (2..=36)
+        .filter(|b| digits.chars().all(|c| map_char_in_base(c, *b).is_some()))
+        .collect()
+}
+
+// map the char at index of a value to its digit value, rejecting digits outside of base
+#[cfg(feature = "std")]
+pub(crate) fn digit_value(c: char, index: usize, base: usize) -> Result<u128, ConvertError> {
+    map_char_in_base(c, base)
+        .map(u128::from)
+        .ok_or(ConvertError::InvalidDigit { ch: c, index, base })
+}
+
+// like `digit_value`, but also reads the one unary digit `1`, which unlike the digits of
+// every other base is not below its base
+#[cfg(feature = "std")]
+fn any_digit_value(c: char, index: usize, base: usize) -> Result<u128, ConvertError> {
+    match (base, c) {
+        (1, '1') => Ok(1),
+        (1, _) => Err(ConvertError::InvalidDigit { ch: c, index, base }),
+        _ => digit_value(c, index, base),
+    }
+}
+
+// move the index of a digit error found in a slice starting offset chars into the value
+#[cfg(feature = "std")]
+pub(crate) fn shift_index(e: ConvertError, offset: usize) -> ConvertError {
+    match e {
+        ConvertError::InvalidDigit { ch, index, base } => ConvertError::InvalidDigit {
+            ch,
+            index: index + offset,
+            base,
+        },
+        e => e,
+    }
+}
+
+/// Renders `in_dec` as a string of digits in `base`
+///
+/// Base 1 (unary) writes `in_dec` copies of `1`, so zero is empty, eg. 5 -> `11111`;
+/// values above `MAX_UNARY` are an `Overflow` there.
+#[cfg(feature = "std")]
+pub fn dec_to_base(in_dec: u128, base: usize) -> Result<String, ConvertError> {
+    if base == 1 {
+        return match in_dec {
+            0..=MAX_UNARY => Ok("1".repeat(in_dec as usize)),
+            _ => Err(ConvertError::Overflow {
+                input: in_dec.to_string(),
+                base,
+            }),
+        };
+    }
+
+    // 128 digits hold any u128, even in binary
+    let mut buf = [0u8; 128];
+    write_digits(in_dec, base, &mut buf)
+        .map(str::to_string)
+        .map_err(|e| from_core_error(e, &in_dec.to_string(), base, 0))
+}
+
+/// Counts the digits of `value` in `base` without rendering them, eg. 65535 in base 16 -> 4
+///
+/// Zero has the single digit `0`, so it counts as 1.
+#[cfg(feature = "std")]
+pub fn digit_count(value: u128, base: usize) -> Result<usize, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    match value {
+        0 => Ok(1),
+        v => Ok(v.ilog(base as u128) as usize + 1),
+    }
+}
+
+/// Sums the digits of `value` in `base`, eg. 9875 in base 10 -> 9 + 8 + 7 + 5 = 29
+#[cfg(feature = "std")]
+pub fn digit_sum(value: u128, base: usize) -> Result<u128, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    Ok(digits(value, base)?.map(u128::from).sum())
+}
+
+/// Repeats `digit_sum` until a single digit is left, eg. 9875 in base 10 -> 29 -> 11 -> 2
+#[cfg(feature = "std")]
+pub fn digital_root(value: u128, base: usize) -> Result<u128, ConvertError> {
+    let mut root: u128 = value;
+    while root >= base as u128 {
+        root = digit_sum(root, base)?;
+    }
+    // a single digit value still has its base checked
+    digit_sum(root, base)
+}
+
+/// Yields the digit values of `value` in `base`, most significant first,
+/// eg. 255 in base 16 -> [15, 15]; zero yields a single 0
+///
+/// `base` may be 2-256, the range a `u8` digit can cover; any other is an `InvalidBase`.
+#[cfg(feature = "std")]
+pub fn digits(value: u128, base: usize) -> Result<impl Iterator<Item = u8>, ConvertError> {
+    if !(2..=256).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+
+    let big_base = base as u128;
+    let mut output: Vec<u8> = Vec::new();
+    let mut cur_val: u128 = value;
+    loop {
+        let res = (cur_val / big_base, cur_val % big_base);
+        cur_val = res.0;
+        output.push(res.1 as u8);
+        if cur_val == 0 {
+            break;
+        }
+    }
+    Ok(output.into_iter().rev())
+}
+
+/// Parses the fractional digits `frac` (without the radix point) written in `base`
+/// into the exact ratio `(numerator, denominator)`, eg. `8` in base 16 -> (8, 16)
+#[cfg(feature = "std")]
+pub fn base_to_frac(frac: &str, base: usize) -> Result<(u128, u128), ConvertError> {
+    if frac.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+
+    // each digit is worth digit * base^-(i+1), so accumulate over a common denominator
+    let big_base = base as u128;
+    let overflow = || ConvertError::Overflow {
+        input: frac.to_string(),
+        base,
+    };
+    let mut num: u128 = 0;
+    let mut den: u128 = 1;
+    for (i, c) in frac.chars().enumerate() {
+        let char_val: u128 = digit_value(c, i, base)?;
+        num = num
+            .checked_mul(big_base)
+            .and_then(|v| v.checked_add(char_val))
+            .ok_or_else(overflow)?;
+        den = den.checked_mul(big_base).ok_or_else(overflow)?;
+    }
+    Ok((num, den))
+}
+
+/// Renders the fraction `num / den` (which must be < 1) as fractional digits in `base`
+///
+/// At most `MAX_FRAC_DIGITS` digits are produced; the expansion is truncated past that,
+/// and trailing zeros are dropped, so a zero fraction renders as an empty string.
+#[cfg(feature = "std")]
+pub fn frac_to_base(num: u128, den: u128, base: usize) -> Result<String, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+
+    let mut output = String::new();
+    let mut cur_num = num;
+    while cur_num != 0 && output.len() < MAX_FRAC_DIGITS {
+        let scaled: u128 =
+            cur_num
+                .checked_mul(base as u128)
+                .ok_or_else(|| ConvertError::Overflow {
+                    input: format!("{}/{}", num, den),
+                    base,
+                })?;
+        output.push(digit_to_char((scaled / den) as u8).unwrap());
+        cur_num = scaled % den;
+    }
+    Ok(output.trim_end_matches('0').to_string())
+}
+
+/// Infers the base of an unsigned literal from its prefix:
+/// `0x` -> 16, `0o` -> 8, `0b` -> 2, `0d` -> 10, a leading `0` -> 8, otherwise 10
+#[cfg(feature = "std")]
+pub fn detect_base(val: &str) -> usize {
+    let mut chars = val.chars();
+    match (chars.next(), chars.next()) {
+        (Some('0'), Some('x')) => 16,
+        (Some('0'), Some('o')) => 8,
+        (Some('0'), Some('b')) => 2,
+        (Some('0'), Some('d')) => 10,
+        (Some('0'), Some('0'..='9')) => 8,
+        _ => 10,
+    }
+}
+
+// render the integer `value` in the base `to`, 2 to MAX_BASE, eg. 255 to 16 -> `ff`
+// this is the numeric core of `convert_value`, which reads the digits and sign around it
+// and checks `to` first; 128 digits hold any u128, even in binary, so it can not fail
+#[cfg(feature = "std")]
+pub(crate) fn convert_integer(value: u128, to: usize) -> String {
+    if to == 10 {
+        return value.to_string();
+    }
+    let mut buf = [0u8; 128];
+    match write_digits(value, to, &mut buf) {
+        Ok(digits) => digits.to_string(),
+        Err(e) => unreachable!("{} has no digits in base {}: {:?}", value, to, e),
+    }
+}
+
+// rewrite the unsigned integer `digits` in its own base without building its value:
+// the prefix and leading zeros are dropped, and each digit is written in its usual form,
+// so letters are lowercased for bases up to 36 and fullwidth digits become ascii
+#[cfg(feature = "std")]
+fn reformat_digits(digits: &str, base: usize) -> Result<String, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    if digits.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+
+    let in_str: &str = strip_base_prefix(digits, base);
+    let prefix_len: usize = digits.len() - in_str.len();
+    let mut output = String::new();
+    for (i, c) in in_str.chars().enumerate() {
+        let digit: u128 = digit_value(c, prefix_len + i, base)?;
+        if digit > 0 || !output.is_empty() {
+            // the digit is below base, so it always maps to a char
+            output.push(digit_to_char(digit as u8).unwrap());
+        }
+    }
+    match output.is_empty() {
+        true => Ok(String::from("0")),
+        false => Ok(output),
+    }
+}
+
+/// Largest exponent magnitude accepted in a scientific-notation decimal, eg. `1e4096`
+#[cfg(feature = "std")]
+pub const MAX_EXPONENT: usize = 4096;
+
+// expand a decimal in scientific notation to its integer digits, eg. `2.5e2` -> `250`
+// a value without a well-formed `e`/`E` exponent, or with any mantissa char other than
+// ascii digits and one point, is None and left for the digit checks to report
+#[cfg(feature = "std")]
+fn expand_exponent(val: &str) -> Result<Option<String>, ConvertError> {
+    let (mantissa, exponent) = match val.split_once(['e', 'E']) {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let (exp_neg, exp_digits) = split_sign(exponent);
+    let all_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if int_part.len() + frac_part.len() == 0
+        || !all_digits(int_part)
+        || !all_digits(frac_part)
+        || exp_digits.is_empty()
+        || !all_digits(exp_digits)
+    {
+        return Ok(None);
+    }
+
+    let overflow = || ConvertError::Overflow {
+        input: val.to_string(),
+        base: 10,
+    };
+    let exp: usize = exp_digits
+        .parse()
+        .ok()
+        .filter(|e| *e <= MAX_EXPONENT)
+        .ok_or_else(overflow)?;
+    // the point moves right by the exponent, past the fraction digits if it can
+    let digits: String = format!("{}{}", int_part, frac_part);
+    let shift: isize = match exp_neg {
+        true => -(exp as isize) - frac_part.len() as isize,
+        false => exp as isize - frac_part.len() as isize,
+    };
+    match shift {
+        0.. => Ok(Some(digits + &"0".repeat(shift as usize))),
+        _ => {
+            let cut: usize = digits.len().saturating_sub(shift.unsigned_abs());
+            match digits[cut..].chars().all(|c| c == '0') {
+                true => Ok(Some(digits[..cut].to_string())),
+                false => Err(ConvertError::NotWhole(val.to_string())),
+            }
+        }
+    }
+}
+
+// read the parts of the literal `val` in `base` that every conversion shares: its sign,
+// its base after any `N#` prefix or detection from a base of 0, its digits without
+// whitespace or separators, and the char index at which those digits start
+#[cfg(feature = "std")]
+pub(crate) fn read_literal(
+    val: &str,
+    base: usize,
+) -> Result<(bool, usize, String, usize), ConvertError> {
+    let leading: usize = val.chars().take_while(char::is_ascii_whitespace).count();
+    let trimmed: &str = trim_whitespace(val);
+    check_radix_separators(trimmed, leading)?;
+
+    // the sign comes first, so a separator before it is not skipped over, then the
+    // separators go, and the radix prefix splits what is left
+    let (is_neg, unsigned) = split_sign(trimmed);
+    let sign_len: usize = trimmed.len() - unsigned.len();
+    let cleaned: String = strip_separators(unsigned);
+    if cleaned.is_empty() && base != 1 {
+        return Err(ConvertError::Empty);
+    }
+
+    let (base, use_val): (usize, &str) = match split_radix_prefix(&cleaned)? {
+        Some((b, digits)) => (b, digits),
+        None => (base, &cleaned),
+    };
+    // the radix prefix is ascii, so its byte length is its char count
+    let radix_len: usize = cleaned.len() - use_val.len();
+
+    let base: usize = match base {
+        0 => detect_base(use_val),
+        b => b,
+    };
+    if !(1..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    check_prefix(use_val, base)?;
+    Ok((is_neg, base, use_val.to_string(), sign_len + radix_len))
+}
+
+/// Converts the (optionally negative) literal `val` from `bases.0` to `bases.1`
+///
+/// An input base of 0 detects the base from the literal's prefix, see `detect_base`,
+/// and an Ada/Erlang style `N#digits` prefix reads the digits in base `N` (2-36) whatever
+/// the declared base, eg. `16#ff` -> 255.
+/// Digit separators (`DIGIT_SEPARATORS`) are ignored anywhere after the sign, except on
+/// either side of a radix point, and so is surrounding whitespace such as a trailing newline;
+/// any other whitespace inside `val` is an error, eg. `-de_ad.be_ef` from 16 to 10 ->
+/// `-57005.7458343505859375`.
+/// A decimal input may be written in scientific notation when it is a whole number,
+/// eg. `2.5e2` from 10 to 16 -> `fa`, while `2.5e0` is a `NotWhole` error.
+#[cfg(feature = "std")]
+pub fn convert_value(bases: (usize, usize), val: &str) -> Result<String, ConvertError> {
+    let (is_neg, in_base, digits, int_offset) = read_literal(val, bases.0)?;
+    if !(1..=MAX_BASE).contains(&bases.1) {
+        return Err(ConvertError::InvalidBase(bases.1));
+    }
+    let bases: (usize, usize) = (in_base, bases.1);
+    let use_val: &str = &digits;
+
+    let expanded: Option<String> = match bases.0 {
+        10 => expand_exponent(strip_base_prefix(use_val, 10))?,
+        _ => None,
+    };
+    let use_val: &str = expanded.as_deref().unwrap_or(use_val);
+
+    // split off the fractional part, if any; either side of the point may be empty,
+    // so `ff.` is `ff` and `.8` is `0.8`, but a bare `.` has no digits at all
+    let (int_val, frac_val) = match use_val.split_once('.') {
+        Some(("", "")) => return Err(ConvertError::Empty),
+        Some((i, f)) => (i, Some(f).filter(|f| !f.is_empty())),
+        None => (use_val, None),
+    };
+
+    // report digit errors by their index in the whole value
+    let frac_offset: usize = int_offset + int_val.chars().count() + 1;
+    // unary zero is already empty
+    let int_val: &str = match int_val {
+        "" if bases.0 != 1 => "0",
+        i => i,
+    };
+
+    // unary has only whole numbers
+    if frac_val.is_some() && (bases.0 == 1 || bases.1 == 1) {
+        return Err(ConvertError::InvalidDigit {
+            ch: '.',
+            index: frac_offset - 1,
+            base: bases.0,
+        });
+    }
+    // unary is limited to u128 values either way, and converting to the same base only
+    // validates and tidies the digits, so it has no size limit either; with the `bignum`
+    // feature a value too large for a u128 is converted as a BigUint instead
+    let mut conv_val: String = match bases {
+        (1, _) | (_, 1) => base_to_dec(int_val, bases.0).and_then(|v| dec_to_base(v, bases.1)),
+        (from, to) if from == to => reformat_digits(int_val, from),
+        (from, to) => match base_to_dec(int_val, from) {
+            Ok(v) => Ok(convert_integer(v, to)),
+            #[cfg(feature = "bignum")]
+            Err(ConvertError::Overflow { .. }) => {
+                bignum::base_to_dec(int_val, from).and_then(|v| bignum::dec_to_base(&v, to))
+            }
+            Err(e) => Err(e),
+        },
+    }
+    .map_err(|e| shift_index(e, int_offset))?;
+
+    if let Some(f) = frac_val {
+        let (num, den) = base_to_frac(f, bases.0).map_err(|e| shift_index(e, frac_offset))?;
+        let frac_digits: String = frac_to_base(num, den, bases.1)?;
+        if !frac_digits.is_empty() {
+            conv_val = conv_val + "." + &frac_digits;
+        }
+    }
+
+    // zero has no sign, so `-0` and `-0.0` come out as `0`
+    let is_zero: bool = conv_val.chars().all(|c| c == '0' || c == '.');
+    match is_neg && !is_zero {
+        true => Ok(String::from("-") + &conv_val),
+        false => Ok(conv_val),
+    }
+}
+
+/// Letter case used for the digits of a converted value
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputCase {
+    #[default]
+    Lower,
+    Upper,
+}
+
+/// Renders the letter digits of `val`, written in `base`, in `case`, eg. `ff` -> `FF`
+///
+/// Bases above 36 use both cases as distinct digits, so their digits are never recased.
+#[cfg(feature = "std")]
+pub fn apply_case(mut val: String, base: usize, case: OutputCase) -> String {
+    if case == OutputCase::Upper && base <= 36 {
+        val.make_ascii_uppercase();
+    }
+    val
+}
+
+/// Like `convert_value`, but renders letter digits in the requested case, eg. `FF`
+///
+/// Bases above 36 use both cases as distinct digits, so their output is never recased.
+#[cfg(feature = "std")]
+pub fn convert_value_cased(
+    bases: (usize, usize),
+    val: &str,
+    case: OutputCase,
+) -> Result<String, ConvertError> {
+    ConversionOptions::new()
+        .uppercase(case == OutputCase::Upper)
+        .convert_with(val, bases.0, bases.1)
+}
+
+/// Checks that `val` has no leading `-`, ignoring surrounding whitespace; a `+` is allowed
+#[cfg(feature = "std")]
+pub fn check_unsigned(val: &str) -> Result<(), ConvertError> {
+    match split_sign(trim_whitespace(val)) {
+        (true, _) => Err(ConvertError::Negative(val.to_string())),
+        (false, _) => Ok(()),
+    }
+}
+
+/// Checks that `val` has exactly `width` digits in `base`, not counting surrounding whitespace,
+/// a sign, the base's own prefix, separators or a radix point, eg. `-0x00ff` has 4 in base 16
+#[cfg(feature = "std")]
+pub fn check_width(val: &str, base: usize, width: usize) -> Result<(), ConvertError> {
+    let unsigned: &str = split_sign(trim_whitespace(val)).1;
+    let found: usize = strip_base_prefix(unsigned, base)
+        .chars()
+        .filter(|c| *c != '.' && !DIGIT_SEPARATORS.contains(c))
+        .count();
+    match found == width {
+        true => Ok(()),
+        false => Err(ConvertError::WidthMismatch {
+            input: val.to_string(),
+            expected: width,
+            found,
+        }),
+    }
+}
+
+/// Counts the digits base `to` needs for every `width`-digit value of base `from`, ie. the
+/// digits of from^width - 1, eg. 4 digits of base 16 take 16 in base 2 and 5 in base 10
+///
+/// The largest value is built as bytes rather than a u128, so the count is exact at any width.
+#[cfg(feature = "std")]
+pub fn equivalent_width(width: usize, from: usize, to: usize) -> Result<usize, ConvertError> {
+    if let Some(b) = [from, to].into_iter().find(|b| !(2..=MAX_BASE).contains(b)) {
+        return Err(ConvertError::InvalidBase(b));
+    }
+    if width == 0 || from == to {
+        return Ok(width);
+    }
+    // from - 1 is below MAX_BASE, so it has a char
+    let top: String = digit_to_char((from - 1) as u8)
+        .unwrap()
+        .to_string()
+        .repeat(width);
+    Ok(bytes_to_base(&base_to_bytes(&top, from)?, to)?.len())
+}
+
+/// Like `convert_value`, but a negative `val` is an error instead of a negative result
+#[cfg(feature = "std")]
+pub fn convert_value_unsigned(bases: (usize, usize), val: &str) -> Result<String, ConvertError> {
+    check_unsigned(val)?;
+    convert_value(bases, val)
+}
+
+/// Returns the conventional literal prefix for `base`: `0x`, `0o`, `0b`, or nothing
+#[cfg(feature = "std")]
+pub fn base_prefix(base: usize) -> &'static str {
+    match base {
+        16 => "0x",
+        8 => "0o",
+        2 => "0b",
+        _ => "",
+    }
+}
+
+/// Like `convert_value`, but prefixes hex, octal and binary output with `0x`/`0o`/`0b`
+/// placed after any sign, eg. `-0xff`
+#[cfg(feature = "std")]
+pub fn convert_value_prefixed(bases: (usize, usize), val: &str) -> Result<String, ConvertError> {
+    ConversionOptions::new()
+        .prefix(true)
+        .convert_with(val, bases.0, bases.1)
+}
+
+/// Converts the integer literal `val` from base `from` to each of the bases in `to`,
+/// parsing it only once, eg. `255` from 10 to [2, 16] -> [`11111111`, `ff`]
+///
+/// A `from` base of 0 detects the base from the literal's prefix, see `detect_base`.
+#[cfg(feature = "std")]
+pub fn convert_to_bases(from: usize, to: &[usize], val: &str) -> Result<Vec<String>, ConvertError> {
+    let from: usize = match from {
+        0 => detect_base(split_sign(trim_whitespace(val)).1),
+        b => b,
+    };
+    let (is_neg, magnitude) = parse_signed(val, from)?;
+    let sign: &str = if is_neg { "-" } else { "" };
+    to.iter()
+        .map(|b| Ok(format!("{}{}", sign, dec_to_base(magnitude, *b)?)))
+        .collect()
+}
+
+/// Renders `value` in `base` as its two's-complement bit pattern at a width of `bits`,
+/// eg. -1 at 8 bits in base 16 -> `ff`
+///
+/// `value` must fit the signed range of the width, ie. -2^(bits-1)..2^(bits-1).
+#[cfg(feature = "std")]
+pub fn to_twos_complement(value: i128, bits: u32, base: usize) -> Result<String, ConvertError> {
+    let out_of_range = ConvertError::OutOfRange { value, bits };
+    if !(1..=128).contains(&bits) {
+        return Err(out_of_range);
+    }
+
+    let min: i128 = i128::MIN >> (128 - bits);
+    let max: i128 = i128::MAX >> (128 - bits);
+    if value < min || value > max {
+        return Err(out_of_range);
+    }
+
+    // reinterpreting the bits of an i128 is the 2^128 modular form; mask it down to 2^bits
+    let mask: u128 = u128::MAX >> (128 - bits);
+    dec_to_base(value as u128 & mask, base)
+}
+
+/// Renders the diminished radix complement of `value` at `width` digits in `base`,
+/// ie. base^width - 1 - value, eg. the nines' complement of 123 at width 5 -> `99876`
+///
+/// Each digit is replaced by base - 1 minus it, so no power of the base is computed.
+/// `value` must fit in `width` digits, otherwise it is an `Overflow`.
+#[cfg(feature = "std")]
+pub fn radix_complement(value: u128, base: usize, width: usize) -> Result<String, ConvertError> {
+    // unary has no digit below 1 to complement against
+    if base == 1 {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    let digits: String = dec_to_base(value, base)?;
+    if digits.len() > width || (width == 0 && value == 0) {
+        return Err(ConvertError::Overflow {
+            input: value.to_string(),
+            base,
+        });
+    }
+    let top: u8 = (base - 1) as u8;
+    // the digits and their complements are all below base, so they always map
+    Ok(pad_digits(&digits, width)
+        .chars()
+        .map(|c| digit_to_char(top - char_to_digit(c).unwrap()).unwrap())
+        .collect())
+}
+
+/// Reads `val` in `base` as a two's-complement bit pattern `bits` wide, the inverse of
+/// `to_twos_complement`, eg. `ffffffff` at 32 bits in base 16 -> -1
+///
+/// `bits` must be 1-128 and the pattern must fit in it, otherwise it is an `Overflow`.
+#[cfg(feature = "std")]
+pub fn from_twos_complement(val: &str, base: usize, bits: u32) -> Result<i128, ConvertError> {
+    let pattern: u128 = base_to_dec(val, base)?;
+    if !(1..=128).contains(&bits) || pattern > u128::MAX >> (128 - bits) {
+        return Err(ConvertError::Overflow {
+            input: val.to_string(),
+            base,
+        });
+    }
+
+    // move the sign bit of the width up to bit 127, then sign-extend it back down
+    let shift: u32 = 128 - bits;
+    Ok(((pattern << shift) as i128) >> shift)
+}
+
+/// Reduces `val` in `base` modulo 2^`bits`, keeping the low `bits` bits as an unsigned
+/// value, eg. `511` at 8 bits -> 255
+///
+/// A negative value wraps like an unsigned register, eg. `-1` at 8 bits -> 255,
+/// and widths of 128 or more leave the value unchanged.
+#[cfg(feature = "std")]
+pub fn reduce_mod_bits(val: &str, base: usize, bits: u32) -> Result<u128, ConvertError> {
+    let (is_neg, magnitude) = parse_signed(val, base)?;
+    let value: u128 = if is_neg {
+        magnitude.wrapping_neg()
+    } else {
+        magnitude
+    };
+    let mask: u128 = u128::MAX.checked_shr(128 - bits.min(128)).unwrap_or(0);
+    Ok(value & mask)
+}
+
+/// Clamps `val` in `base` to the range 0 to `max`, eg. `500` with a max of 255 -> 255
+///
+/// Unlike `reduce_mod_bits` nothing wraps: a negative value becomes 0, and any larger
+/// value, even one too large for a u128, becomes `max`.
+#[cfg(feature = "std")]
+pub fn saturate(val: &str, base: usize, max: u128) -> Result<u128, ConvertError> {
+    match parse_signed(val, base) {
+        Ok((true, _)) => Ok(0),
+        Ok((false, magnitude)) => Ok(magnitude.min(max)),
+        Err(ConvertError::Overflow { .. }) => {
+            // the overflow stops parsing early, so the remaining digits are checked here
+            check_literal_digits(val, base)?;
+            match split_sign(trim_whitespace(val)).0 {
+                true => Ok(0),
+                false => Ok(max),
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Left-pads the integer digits of `s` with `0` to at least `width` digits, keeping
+/// any sign and `0x`/`0o`/`0b` prefix in front, eg. `-5` at width 4 -> `-0005`
+///
+/// Values already `width` digits or longer are returned unchanged.
+#[cfg(feature = "std")]
+pub fn pad_digits(s: &str, width: usize) -> String {
+    let (sign, unsigned) = match s.strip_prefix('-') {
+        Some(v) => ("-", v),
+        None => ("", s),
+    };
+    let digits: &str = ["0x", "0o", "0b"]
+        .iter()
+        .find_map(|p| unsigned.strip_prefix(p))
+        .unwrap_or(unsigned);
+    let prefix: &str = &unsigned[..unsigned.len() - digits.len()];
+
+    let int_len: usize = digits.find('.').unwrap_or(digits.len());
+    let zeros: String = "0".repeat(width.saturating_sub(int_len));
+    format!("{}{}{}{}", sign, prefix, zeros, digits)
+}
+
+/// Inserts `sep` between every `group_size` digits of `s`, counting from the right of
+/// the integer part, eg. `11111111` -> `1111_1111`; any sign and fraction are left as is
+#[cfg(feature = "std")]
+pub fn group_digits(s: &str, group_size: usize, sep: char) -> String {
+    let (sign, unsigned) = match s.strip_prefix('-') {
+        Some(v) => ("-", v),
+        None => ("", s),
+    };
+    let (int_part, frac_part) = match unsigned.find('.') {
+        Some(i) => unsigned.split_at(i),
+        None => (unsigned, ""),
+    };
+    if group_size == 0 {
+        return s.to_string();
+    }
+
+    let digits: Vec<char> = int_part.chars().collect();
+    let mut output = String::from(sign);
+    for (i, c) in digits.iter().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(group_size) {
+            output.push(sep);
+        }
+        output.push(*c);
+    }
+    output + frac_part
+}
+
+/// Appends `base` to `value` in Unicode subscript digits, eg. `ff` in base 16 -> `ff₁₆`
+#[cfg(feature = "std")]
+pub fn format_with_subscript_base(value: &str, base: usize) -> String {
+    let subscript: String = base
+        .to_string()
+        .chars()
+        // subscript zero is U+2080, with the other digits following in order
+        .filter_map(|c| c.to_digit(10).and_then(|d| char::from_u32(0x2080 + d)))
+        .collect();
+    format!("{}{}", value, subscript)
+}
+
+// ensure the alphabet has no repeated symbols and enough of them for base
+#[cfg(feature = "std")]
+fn check_alphabet(alphabet: &[char], base: usize) -> Result<(), ConvertError> {
+    for (i, c) in alphabet.iter().enumerate() {
+        if alphabet[..i].contains(c) {
+            return Err(ConvertError::DuplicateSymbol(*c));
+        }
+    }
+    match (2..=alphabet.len()).contains(&base) {
+        true => Ok(()),
+        false => Err(ConvertError::InvalidBase(base)),
+    }
+}
+
+/// Parses `in_val` written in `base`, where the first `base` symbols of `alphabet`
+/// are the digits in order, eg. `"ABCD"` makes `B` worth 1
+#[cfg(feature = "std")]
+pub fn base_to_dec_with_alphabet(
+    in_val: &str,
+    base: usize,
+    alphabet: &str,
+) -> Result<u128, ConvertError> {
+    let symbols: Vec<char> = alphabet.chars().collect();
+    check_alphabet(&symbols, base)?;
+    if in_val.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+
+    let mut out_val: u128 = 0;
+    for (i, c) in in_val.chars().enumerate() {
+        let char_val: usize =
+            symbols[..base]
+                .iter()
+                .position(|s| *s == c)
+                .ok_or(ConvertError::InvalidDigit {
+                    ch: c,
+                    index: i,
+                    base,
+                })?;
+        out_val = out_val
+            .checked_mul(base as u128)
+            .and_then(|v| v.checked_add(char_val as u128))
+            .ok_or_else(|| ConvertError::Overflow {
+                input: in_val.to_string(),
+                base,
+            })?;
+    }
+    Ok(out_val)
+}
+
+/// Renders `in_dec` in `base` using the first `base` symbols of `alphabet` as digits
+#[cfg(feature = "std")]
+pub fn dec_to_base_with_alphabet(
+    in_dec: u128,
+    base: usize,
+    alphabet: &str,
+) -> Result<String, ConvertError> {
+    let symbols: Vec<char> = alphabet.chars().collect();
+    check_alphabet(&symbols, base)?;
+    if in_dec == 0 {
+        return Ok(symbols[0].to_string());
+    }
+
+    // least significant first, reversed once at the end
+    let mut output: Vec<char> = Vec::new();
+    let mut cur_val: u128 = in_dec;
+    while cur_val != 0 {
+        output.push(symbols[(cur_val % base as u128) as usize]);
+        cur_val /= base as u128;
+    }
+    Ok(output.into_iter().rev().collect())
+}
+
+/// Converts `val` from `bases.0` to `bases.1` with `alphabet` supplying the digits
+/// of both sides instead of `0-9a-zA-Z`
+///
+/// Every symbol is treated as a digit, so signs and radix points are not supported.
+#[cfg(feature = "std")]
+pub fn convert_value_with_alphabet(
+    bases: (usize, usize),
+    val: &str,
+    alphabet: &str,
+) -> Result<String, ConvertError> {
+    let dec_val: u128 = base_to_dec_with_alphabet(val, bases.0, alphabet)?;
+    dec_to_base_with_alphabet(dec_val, bases.1, alphabet)
+}
+
+/// Converts `value` written in the symbols of `from_alphabet` to the symbols of
+/// `to_alphabet`, where each alphabet's length is its base, eg. `TTTT` in `ACGT` -> `255`
+/// in `0123456789`
+#[cfg(feature = "std")]
+pub fn transcode(
+    value: &str,
+    from_alphabet: &str,
+    to_alphabet: &str,
+) -> Result<String, ConvertError> {
+    let from_base: usize = from_alphabet.chars().count();
+    let to_base: usize = to_alphabet.chars().count();
+    let dec_val: u128 = base_to_dec_with_alphabet(value, from_base, from_alphabet)?;
+    dec_to_base_with_alphabet(dec_val, to_base, to_alphabet)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*; // brings main scope into test scope
+
+    #[test]
+    fn convert_error_test() {
+        let vals: Vec<(ConvertError, &str)> = vec![
+            (
+                ConvertError::InvalidDigit {
+                    ch: 'g',
+                    index: 1,
+                    base: 16,
+                },
+                "`g` at index 1 is not a valid digit in base 16",
+            ),
+            (
+                ConvertError::InvalidBase(63),
+                "63 is not a valid base; bases: 1-62 allowed",
+            ),
+            (
+                ConvertError::InvalidNegativeBase(-1),
+                "-1 is not a valid negative base; bases: -2 to -62 allowed",
+            ),
+            (
+                ConvertError::DuplicateSymbol('a'),
+                "`a` appears more than once in the alphabet",
+            ),
+            (
+                ConvertError::OutOfRange {
+                    value: 256,
+                    bits: 8,
+                },
+                "256 does not fit in 8 bits",
+            ),
+            (
+                ConvertError::RomanRange(4000),
+                "4000 is outside the roman numeral range 1-3999",
+            ),
+            (
+                ConvertError::InvalidRoman("IIII".to_string()),
+                "`IIII` is not a valid roman numeral",
+            ),
+            (ConvertError::Empty, "value is empty"),
+            (
+                ConvertError::Overflow {
+                    input: "ff".to_string(),
+                    base: 16,
+                },
+                "`ff` in base 16 is too large to convert",
+            ),
+            (ConvertError::ZeroDenominator, "denominator is zero"),
+            (
+                ConvertError::PrefixBaseMismatch {
+                    prefix: 16,
+                    base: 8,
+                },
+                "`0x` prefix means base 16, but the value is declared as base 8",
+            ),
+            (
+                ConvertError::Negative("-5".to_string()),
+                "`-5` is negative; only unsigned values are accepted",
+            ),
+            (
+                ConvertError::MixedRadixDigit {
+                    digit: 60,
+                    index: 1,
+                    radix: 60,
+                },
+                "60 at index 1 is not a valid digit for radix 60",
+            ),
+            (
+                ConvertError::RadixCount {
+                    digits: 2,
+                    radices: 3,
+                },
+                "2 digits were given for 3 radices",
+            ),
+            (
+                ConvertError::WidthMismatch {
+                    input: "ff".to_string(),
+                    expected: 4,
+                    found: 2,
+                },
+                "`ff` has 2 digits, but exactly 4 are required",
+            ),
+            (
+                ConvertError::UnknownLanguage("go".to_string()),
+                "`go` is not a known language; languages: rust, c",
+            ),
+            (
+                ConvertError::InvalidSuffix("u32".to_string()),
+                "`u32` is not a valid integer literal suffix",
+            ),
+            (
+                ConvertError::MissingArgs(2),
+                "expected <inbase> <outbase> <value>..., but 2 args were given",
+            ),
+            (
+                ConvertError::InvalidBaseArg("ten".to_string()),
+                "`ten` can not be parsed as a base",
+            ),
+            (
+                ConvertError::InvalidUtf8 { index: 1 },
+                "the bytes from index 1 are not valid UTF-8",
+            ),
+            (
+                ConvertError::NotWhole("1.5e0".to_string()),
+                "`1.5e0` is not a whole number",
+            ),
+        ];
+        for v in vals {
+            assert_eq!(v.0.to_string(), v.1);
+            // it boxes into a trait object and keeps its message
+            let boxed: Box<dyn std::error::Error> = Box::new(v.0);
+            assert_eq!(boxed.to_string(), v.1);
+            assert!(boxed.source().is_none());
+        }
+
+        // and `?` converts it into a boxed error
+        fn parse(val: &str) -> Result<u128, Box<dyn std::error::Error>> {
+            Ok(base_to_dec(val, 16)?)
+        }
+        assert_eq!(parse("ff").unwrap(), 255);
+        let e: Box<dyn std::error::Error> = parse("fg").unwrap_err();
+        assert_eq!(
+            e.downcast_ref::<ConvertError>(),
+            Some(&ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 1,
+                base: 16
+            })
+        );
+    }
+
+    #[test]
+    fn unary_test() {
+        let vals: Vec<(u128, &str)> = vec![(0, ""), (1, "1"), (5, "11111"), (12, "111111111111")];
+        for v in vals {
+            assert_eq!(dec_to_base(v.0, 1), Ok(v.1.to_string()));
+            assert_eq!(base_to_dec(v.1, 1), Ok(v.0));
+        }
+        let conversions: Vec<((usize, usize), &str, &str)> = vec![
+            ((10, 1), "5", "11111"),
+            ((1, 10), "11111", "5"),
+            ((1, 16), "1111_1111_1111_1111", "10"),
+            ((2, 1), "-11", "-111"),
+            ((1, 1), "1'1", "11"),
+            ((1, 10), "", "0"),
+            ((10, 1), "-0", ""),
+        ];
+        for v in conversions {
+            assert_eq!(convert_value(v.0, v.1), Ok(v.2.to_string()));
+        }
+
+        assert_eq!(
+            base_to_dec("1101", 1),
+            Err(ConvertError::InvalidDigit {
+                ch: '0',
+                index: 2,
+                base: 1
+            })
+        );
+        assert_eq!(
+            convert_value((10, 1), "2.5"),
+            Err(ConvertError::InvalidDigit {
+                ch: '.',
+                index: 1,
+                base: 10
+            })
+        );
+        assert_eq!(dec_to_base(MAX_UNARY, 1).map(|s| s.len()), Ok(1 << 20));
+        assert_eq!(
+            dec_to_base(MAX_UNARY + 1, 1),
+            Err(ConvertError::Overflow {
+                input: (MAX_UNARY + 1).to_string(),
+                base: 1
+            })
+        );
+    }
+
+    #[test]
+    fn fullwidth_digit_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((10, 10), "\u{FF11}\u{FF12}\u{FF13}", "123"),
+            ((10, 16), "\u{FF11}\u{FF12}\u{FF13}", "7b"),
+            ((16, 10), "\u{FF26}\u{FF26}", "255"),
+            ((16, 10), "\u{FF46}f", "255"),
+            ((10, 2), "-\u{FF15}", "-101"),
+            ((62, 10), "\u{FF3A}", "61"),
+        ];
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1), Ok(v.2.to_string()));
+            assert_eq!(check_digits(v.1, v.0 .0), Ok(()));
+        }
+        assert_eq!(base_to_dec("\u{FF11}\u{FF12}\u{FF13}", 10), Ok(123));
+        assert_eq!(char_to_digit('\u{FF10}'), Some(0));
+        // fullwidth digits are still checked against the base
+        assert_eq!(
+            base_to_dec("1\u{FF18}", 8),
+            Err(ConvertError::InvalidDigit {
+                ch: '\u{FF18}',
+                index: 1,
+                base: 8
+            })
+        );
+    }
+
+    #[test]
+    fn non_ascii_digit_test() {
+        // an emoji, a combining acute accent after a valid `e`, and the replacement char
+        // that stands in for unpaired surrogates in an argument
+        let vals: Vec<(&str, usize, char, usize, &str)> = vec![
+            ("1\u{1F600}", 10, '\u{1F600}', 1, "U+1F600 at index 1"),
+            ("cafe\u{301}", 16, '\u{301}', 4, "U+0301 at index 4"),
+            ("\u{FFFD}", 16, '\u{FFFD}', 0, "U+FFFD at index 0"),
+        ];
+        for v in vals {
+            let e = || ConvertError::InvalidDigit {
+                ch: v.2,
+                index: v.3,
+                base: v.1,
+            };
+            assert_eq!(base_to_dec(v.0, v.1), Err(e()));
+            assert_eq!(check_digits(v.0, v.1), Err(e()));
+            assert_eq!(
+                e().to_string(),
+                format!(
+                    "non-ASCII character {} is not a valid digit in base {}",
+                    v.4, v.1
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn dec_to_base_test() {
+        let vals: Vec<(u128, &str)> = vec![
+            (0, "0"),
+            (10, "a"),
+            (15, "f"),
+            (256, "100"),
+            (4660, "1234"),
+            (65535, "ffff"),
+        ];
+
+        for v in vals {
+            assert_eq!(dec_to_base(v.0, 16).unwrap(), v.1);
+        }
+    }
+
+    #[test]
+    fn digits_test() {
+        let vals: Vec<(u128, usize, Vec<u8>)> = vec![
+            (255, 16, vec![15, 15]),
+            (0, 10, vec![0]),
+            (4660, 16, vec![1, 2, 3, 4]),
+            (5, 2, vec![1, 0, 1]),
+            (3843, 62, vec![61, 61]),
+            (65535, 256, vec![255, 255]),
+        ];
+
+        for v in vals {
+            assert_eq!(digits(v.0, v.1).map(Iterator::collect::<Vec<u8>>), Ok(v.2));
+        }
+    }
+
+    #[test]
+    fn digit_sum_test() {
+        let vals: Vec<(u128, usize, u128, u128)> = vec![
+            (9875, 10, 29, 2),
+            (0, 10, 0, 0),
+            (7, 10, 7, 7),
+            (255, 16, 30, 15),
+            (255, 2, 8, 1),
+            (u128::MAX, 2, 128, 1),
+        ];
+        for v in vals {
+            assert_eq!(digit_sum(v.0, v.1), Ok(v.2));
+            assert_eq!(digital_root(v.0, v.1), Ok(v.3));
+        }
+        // the digital root of a nonzero value is 1 + (value - 1) mod (base - 1)
+        for base in 2..=MAX_BASE {
+            for v in (1..2000).chain([u128::MAX - 1, u128::MAX]) {
+                let expected: u128 = 1 + (v - 1) % (base as u128 - 1);
+                assert_eq!(digital_root(v, base), Ok(expected));
+            }
+        }
+        assert_eq!(digit_sum(1, 63), Err(ConvertError::InvalidBase(63)));
+        assert_eq!(digital_root(1, 1), Err(ConvertError::InvalidBase(1)));
+    }
+
+    #[test]
+    fn digit_count_test() {
+        let vals: Vec<(u128, usize, usize)> = vec![
+            (0, 10, 1),
+            (65535, 16, 4),
+            (65536, 16, 5),
+            (255, 2, 8),
+            (u128::MAX, 2, 128),
+            (u128::MAX, 62, 22),
+        ];
+        for v in vals {
+            assert_eq!(digit_count(v.0, v.1), Ok(v.2));
+        }
+        // matches the length of the rendered digits, around every power of the base
+        for base in 2..=MAX_BASE {
+            let mut values: Vec<u128> = (0..200).collect();
+            let mut power: u128 = base as u128;
+            while let Some(next) = power.checked_mul(base as u128) {
+                values.extend([power - 1, power, power + 1]);
+                power = next;
+            }
+            for v in values {
+                assert_eq!(
+                    digit_count(v, base),
+                    Ok(dec_to_base(v, base).unwrap().len())
+                );
+            }
+        }
+        assert_eq!(digit_count(1, 63), Err(ConvertError::InvalidBase(63)));
+    }
+
+    #[test]
+    fn digits_invalid_base_test() {
+        for base in [0, 1, 257] {
+            assert_eq!(
+                digits(10, base).map(Iterator::count),
+                Err(ConvertError::InvalidBase(base))
+            );
+        }
+    }
+
+    #[test]
+    fn base_to_dec_test() {
+        let vals: Vec<(&str, u128)> = vec![
+            ("0", 0),
+            ("a", 10),
+            ("f", 15),
+            ("100", 256),
+            ("1234", 4660),
+            ("ffff", 65535),
+            ("ffffffffffffffff", u64::MAX as u128),
+            ("ffffffffffffffffffffffffffffffff", u128::MAX),
+        ];
+
+        for v in vals {
+            assert_eq!(base_to_dec(v.0, 16).unwrap(), v.1);
+        }
+
+        // bases outside 1 to MAX_BASE are rejected like everywhere else
+        assert_eq!(base_to_dec("12", 100), Err(ConvertError::InvalidBase(100)));
+        assert_eq!(base_to_dec("0", 0), Err(ConvertError::InvalidBase(0)));
+        assert_eq!(base_to_dec("11", 1), Ok(2));
+    }
+
+    // the positional-power parser base_to_dec used before Horner's method, to compare against
+    fn pow_base_to_dec(in_val: &str, base: usize) -> Option<u128> {
+        let digit_count: usize = in_val.chars().count();
+        let mut out_val: u128 = 0;
+        for (i, c) in in_val.chars().enumerate() {
+            let pval: u128 = (base as u128).checked_pow((digit_count - 1 - i) as u32)?;
+            let char_val: u128 = map_char_in_base(c, base)?.into();
+            out_val = out_val.checked_add(char_val.checked_mul(pval)?)?;
+        }
+        Some(out_val)
+    }
+
+    #[test]
+    fn base_to_dec_horner_test() {
+        // long inputs near the top of the u128 range, in every base
+        for base in 2..=MAX_BASE {
+            let mut value: u128 = u128::MAX;
+            while value > 0 {
+                let digits: String = dec_to_base(value, base).unwrap();
+                assert_eq!(
+                    base_to_dec(&digits, base).ok(),
+                    pow_base_to_dec(&digits, base)
+                );
+                assert_eq!(base_to_dec(&digits, base), Ok(value));
+                value /= 7;
+            }
+        }
+
+        // leading zeros used to overflow the power of the first digit with a value that fits
+        let padded: String = "0".repeat(200) + "ff";
+        assert_eq!(pow_base_to_dec(&padded, 16), None);
+        assert_eq!(base_to_dec(&padded, 16), Ok(255));
+
+        let too_big: String = "1".to_string() + &"0".repeat(32);
+        assert_eq!(
+            base_to_dec(&too_big, 16),
+            Err(ConvertError::Overflow {
+                input: too_big.clone(),
+                base: 16
+            })
+        );
+    }
+
+    #[test]
+    fn digit_to_char_test() {
+        let vals: Vec<(u8, Option<char>)> = vec![
+            (0, Some('0')),
+            (5, Some('5')),
+            (10, Some('a')),
+            (15, Some('f')),
+            (35, Some('z')),
+            (36, Some('A')),
+            (61, Some('Z')),
+            (62, None),
+            (100, None),
+            (255, None),
+        ];
+
+        for v in vals {
+            assert_eq!(digit_to_char(v.0), v.1);
+        }
+        // every digit maps back to its value
+        for i in 0..62 {
+            assert_eq!(char_to_digit(digit_to_char(i).unwrap()), Some(i));
+        }
+        #[allow(deprecated)]
+        {
+            assert_eq!(char_map(35), Some('z'));
+            assert_eq!(map_char('Z'), Some(61));
+        }
+    }
+
+    #[test]
+    fn char_to_digit_test() {
+        let vals: Vec<(char, Option<u8>)> = vec![
+            ('a', Some(10)),
+            ('0', Some(0)),
+            ('f', Some(15)),
+            ('5', Some(5)),
+            ('z', Some(35)),
+            ('A', Some(36)),
+            ('Z', Some(61)),
+            ('.', None),
+        ];
+
+        for v in vals {
+            assert_eq!(char_to_digit(v.0), v.1);
+        }
+    }
+
+    #[test]
+    fn convert_value_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((10, 10), "100", "100"),
+            ((10, 16), "10", "a"),
+            ((10, 16), "4660", "1234"),
+            ((10, 8), "668", "1234"),
+            ((8, 10), "1234", "668"),
+            ((8, 16), "100", "40"),
+            ((16, 10), "0xffff", "65535"),
+            ((10, 16), "-10", "-a"),
+            ((10, 30), "1000", "13a"),
+            ((30, 10), "13a", "1000"),
+            ((16, 10), "ffffffffffffffff", "18446744073709551615"),
+            ((16, 10), "FF", "255"),
+            ((10, 62), "3843", "ZZ"),
+            ((62, 10), "ZZ", "3843"),
+            ((10, 62), "1000000", "4c92"),
+            ((62, 10), "4c92", "1000000"),
+            ((62, 16), "zZ", "8b7"),
+        ];
+
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1).unwrap(), v.2);
+        }
+    }
+
+    #[test]
+    fn map_char_in_base_test() {
+        let vals: Vec<(char, usize, Option<u8>)> = vec![
+            ('7', 8, Some(7)),
+            ('8', 8, None),
+            ('f', 16, Some(15)),
+            ('F', 16, Some(15)),
+            ('g', 16, None),
+            ('1', 2, Some(1)),
+            ('z', 36, Some(35)),
+            ('A', 37, Some(36)),
+            ('B', 37, None),
+            ('-', 16, None),
+        ];
+
+        for v in vals {
+            assert_eq!(map_char_in_base(v.0, v.1), v.2);
+        }
+
+        let vals: Vec<((usize, usize), &str, char, usize)> = vec![
+            ((8, 10), "1238", '8', 3),
+            ((8, 10), "8123", '8', 0),
+            ((16, 10), "0xfg", 'g', 3),
+            ((16, 10), "-ff.fg", 'g', 5),
+            ((10, 16), "-1g", 'g', 2),
+        ];
+
+        for v in vals {
+            assert_eq!(
+                convert_value(v.0, v.1),
+                Err(ConvertError::InvalidDigit {
+                    ch: v.2,
+                    index: v.3,
+                    base: v.0 .0
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn check_digits_test() {
+        let vals: Vec<(&str, usize, Option<usize>)> = vec![
+            ("a", 11, None),
+            ("A", 11, None),
+            ("b", 11, Some(0)),
+            ("B", 11, Some(0)),
+            ("-1A.A", 11, None),
+            ("-1B.A", 11, Some(2)),
+            ("1_1-", 2, Some(3)),
+            ("Z", 36, None),
+            ("Z", 35, Some(0)),
+            ("zZ", 62, None),
+            ("A", 37, None),
+            ("B", 37, Some(0)),
+            ("2.5e2", 10, None),
+            ("1_0E-1", 10, None),
+            ("2.5e0", 10, None),
+            ("1e", 10, Some(1)),
+            ("1x5e3", 10, Some(1)),
+        ];
+
+        for v in vals {
+            let expected = match v.2 {
+                Some(index) => Err(ConvertError::InvalidDigit {
+                    ch: v.0.chars().nth(index).unwrap(),
+                    index,
+                    base: v.1,
+                }),
+                None => Ok(()),
+            };
+            assert_eq!(check_digits(v.0, v.1), expected);
+        }
+    }
+
+    #[test]
+    fn canonicalize_test() {
+        let vals: Vec<(&str, usize, &str)> = vec![
+            ("00ff", 16, "ff"),
+            ("ff\n", 16, "ff"),
+            (" -007\t", 10, "-7"),
+            ("000", 10, "0"),
+            ("-007", 10, "-7"),
+            ("-000", 10, "0"),
+            ("00FF", 16, "ff"),
+            ("00Zz", 62, "Zz"),
+            ("0000_0101", 2, "101"),
+            ("00.50", 10, "0.50"),
+            ("7", 8, "7"),
+        ];
+        for v in vals {
+            assert_eq!(canonicalize(v.0, v.1), Ok(v.2.to_string()));
+        }
+        assert_eq!(canonicalize("", 10), Err(ConvertError::Empty));
+        assert_eq!(canonicalize("1", 63), Err(ConvertError::InvalidBase(63)));
+        assert_eq!(
+            canonicalize("00g", 16),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 2,
+                base: 16
+            })
+        );
+        // the digits are rewritten as written, so an exponent is not one of them
+        assert_eq!(
+            canonicalize("1e3", 10),
+            Err(ConvertError::InvalidDigit {
+                ch: 'e',
+                index: 1,
+                base: 10
+            })
+        );
+    }
+
+    #[test]
+    fn values_equal_test() {
+        let vals: Vec<(&str, usize, &str, usize, bool)> = vec![
+            ("ff", 16, "11111111", 2, true),
+            ("FF", 16, "255", 10, true),
+            ("-ff", 16, "-255", 10, true),
+            ("-0", 10, "0", 2, true),
+            ("1_0000", 2, "10", 16, true),
+            ("Zz", 62, "zZ", 62, false),
+            ("ff", 16, "-255", 10, false),
+            ("100", 10, "100", 16, false),
+        ];
+        for v in vals {
+            assert_eq!(values_equal((v.0, v.1), (v.2, v.3)), Ok(v.4));
+        }
+        assert_eq!(
+            values_equal(("-fg", 16), ("1", 10)),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 2,
+                base: 16
+            })
+        );
+        assert_eq!(
+            values_equal(("1", 10), ("1", 1)),
+            Err(ConvertError::InvalidBase(1))
+        );
+    }
+
+    #[test]
+    fn prefix_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((16, 10), "0xff", "255"),
+            ((8, 10), "0o17", "15"),
+            ((2, 10), "0b101", "5"),
+            ((16, 10), "-0xff", "-255"),
+            // a bare prefix is only digits, where they are valid
+            ((36, 10), "0x", "33"),
+            ((16, 10), "0b", "11"),
+        ];
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1), Ok(v.2.to_string()));
+            assert_eq!(check_digits(v.1, v.0 .0), Ok(()));
+        }
+
+        // a prefix for another base is an error, even where its chars are valid digits
+        let vals: Vec<((usize, usize), &str, usize)> = vec![
+            ((8, 10), "0xff", 16),
+            ((16, 10), "0b1", 2),
+            ((10, 16), "0x10", 16),
+            ((2, 10), "-0o1", 8),
+            ((36, 10), "0o1", 8),
+            ((10, 10), "+0b1.1", 2),
+        ];
+        for v in vals {
+            let err = || ConvertError::PrefixBaseMismatch {
+                prefix: v.2,
+                base: v.0 .0,
+            };
+            assert_eq!(convert_value(v.0, v.1), Err(err()));
+            assert_eq!(check_digits(v.1, v.0 .0), Err(err()));
+        }
+        assert_eq!(
+            ConvertError::PrefixBaseMismatch {
+                prefix: 16,
+                base: 8
+            }
+            .to_string(),
+            "`0x` prefix means base 16, but the value is declared as base 8"
+        );
+        assert_eq!(
+            convert_value((10, 16), "0x"),
+            Err(ConvertError::InvalidDigit {
+                ch: 'x',
+                index: 1,
+                base: 10
+            })
+        );
+    }
+
+    #[test]
+    fn radix_prefix_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((10, 10), "16#ff", "255"),
+            ((0, 10), "16#ff", "255"),
+            ((16, 10), "8#17", "15"),
+            ((10, 2), "-2#101", "-101"),
+            ((10, 10), "36#z.i", "35.5"),
+            ((10, 10), "0d42", "42"),
+            ((10, 16), "-0d255", "-ff"),
+        ];
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1), Ok(v.2.to_string()));
+            assert_eq!(check_digits(v.1, v.0 .0), Ok(()));
+        }
+
+        let vals: Vec<(&str, ConvertError)> = vec![
+            ("37#1", ConvertError::InvalidBase(37)),
+            ("1#1", ConvertError::InvalidBase(1)),
+            (
+                "16#fg",
+                ConvertError::InvalidDigit {
+                    ch: 'g',
+                    index: 4,
+                    base: 16,
+                },
+            ),
+            (
+                "f#1",
+                ConvertError::InvalidDigit {
+                    ch: '#',
+                    index: 1,
+                    base: 16,
+                },
+            ),
+        ];
+        for v in vals {
+            assert_eq!(convert_value((16, 10), v.0), Err(v.1));
+        }
+        // 0d is only a prefix in base 10, elsewhere it is digits
+        assert_eq!(convert_value((16, 10), "0d"), Ok("13".to_string()));
+    }
+
+    #[test]
+    fn detect_base_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((0, 10), "0xff", "255"),
+            ((0, 10), "0b101", "5"),
+            ((0, 10), "0o17", "15"),
+            ((0, 10), "017", "15"),
+            ((0, 10), "42", "42"),
+            ((0, 10), "0", "0"),
+            ((0, 10), "0.5", "0.5"),
+            ((0, 16), "-0b1111", "-f"),
+            ((0, 10), "0d42", "42"),
+        ];
+
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1).unwrap(), v.2);
+        }
+    }
+
+    #[test]
+    fn convert_value_separators_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((2, 10), "1111_1111", "255"),
+            ((16, 10), "de_ad_be_ef", "3735928559"),
+            ((16, 10), "ff ff", "65535"),
+            ((16, 10), "_ff__ff_", "65535"),
+            ((10, 16), "-1_000", "-3e8"),
+            ((0, 10), "0x_ff", "255"),
+            ((2, 10), "0.01_1", "0.375"),
+            ((10, 10), "1'000'000", "1000000"),
+            ((16, 10), "ff'ff", "65535"),
+        ];
+
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1).unwrap(), v.2);
+        }
+        assert_eq!(convert_value((10, 16), "__"), Err(ConvertError::Empty));
+        assert_eq!(check_digits("1111_1111", 2), Ok(()));
+        assert_eq!(check_digits("ff'ff", 16), Ok(()));
+    }
+
+    #[test]
+    fn reverse_digits_test() {
+        let vals: Vec<(&str, usize, &str)> = vec![
+            ("ff0", 16, "0ff"),
+            ("-0x12f", 16, "-0xf21"),
+            ("+0b110", 2, "+0b011"),
+            ("0b1", 16, "1b0"),
+            (" 123\n", 10, "321"),
+            ("7", 8, "7"),
+        ];
+        for v in vals {
+            assert_eq!(reverse_digits(v.0, v.1), v.2);
+            assert_eq!(reverse_digits(v.2, v.1), v.0.trim());
+        }
+
+        // converting least significant digit first: reverse, convert, reverse back
+        let le = |bases: (usize, usize), val: &str| {
+            convert_value(bases, &reverse_digits(val, bases.0)).map(|c| reverse_digits(&c, bases.1))
+        };
+        assert_eq!(convert_value((16, 10), "01"), Ok("1".to_string()));
+        assert_eq!(le((16, 10), "01"), Ok("61".to_string()));
+        assert_eq!(le((10, 16), "61"), Ok("01".to_string()));
+        assert_eq!(le((10, 2), "-21"), Ok("-0011".to_string()));
+    }
+
+    #[test]
+    fn shift_radix_test() {
+        let vals: Vec<(&str, usize, i32, &str)> = vec![
+            ("ff.0", 16, -1, "f.f"),
+            ("ff", 16, 2, "ff00"),
+            ("f.f", 16, 1, "ff"),
+            ("0x1.8", 16, -3, "0.0018"),
+            ("A.B", 16, 0, "a.b"),
+            ("101.1", 2, 1, "1011"),
+            ("101.1", 2, -4, "0.01011"),
+            ("0.001", 2, 3, "1"),
+            ("-11", 2, -1, "-1.1"),
+            ("-0.0", 2, 5, "0"),
+            ("1_0000", 2, -4, "1"),
+        ];
+        for v in vals {
+            assert_eq!(shift_radix(v.0, v.1, v.2), Ok(v.3.to_string()));
+        }
+        // shifting back restores the value
+        assert_eq!(
+            shift_radix("1.5", 10, 3).and_then(|s| shift_radix(&s, 10, -3)),
+            Ok("1.5".to_string())
+        );
+        assert_eq!(
+            shift_radix("fg", 16, 1),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 1,
+                base: 16
+            })
+        );
+        assert_eq!(shift_radix("-", 16, 1), Err(ConvertError::Empty));
+        assert_eq!(
+            shift_radix("1", 2, 4096),
+            Ok(format!("1{}", "0".repeat(4096)))
+        );
+        assert_eq!(
+            shift_radix("1", 2, -4096),
+            Ok(format!("0.{}1", "0".repeat(4095)))
+        );
+        for places in [4097, -4097, i32::MAX, i32::MIN] {
+            assert_eq!(
+                shift_radix("ff", 16, places),
+                Err(ConvertError::Overflow {
+                    input: "ff".to_string(),
+                    base: 16
+                })
+            );
+        }
+        assert_eq!(
+            shift_radix("1e3", 10, 1),
+            Err(ConvertError::InvalidDigit {
+                ch: 'e',
+                index: 1,
+                base: 10
+            })
+        );
+        assert_eq!(
+            shift_radix("1.0.1", 2, 1),
+            Err(ConvertError::InvalidDigit {
+                ch: '.',
+                index: 3,
+                base: 2
+            })
+        );
+    }
+
+    #[test]
+    fn plus_sign_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((10, 16), "+255", "ff"),
+            ((10, 10), "+0", "0"),
+            ((0, 10), "+0x10", "16"),
+            ((16, 10), "+0xff", "255"),
+            ((2, 10), "+0.1", "0.5"),
+            ((10, 10), " +16#ff ", "255"),
+        ];
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1), Ok(v.2.to_string()));
+        }
+        assert_eq!(check_digits("+ff", 16), Ok(()));
+        assert_eq!(canonicalize("+007", 10), Ok("7".to_string()));
+        assert_eq!(canonicalize("-0b0110", 2), Ok("-110".to_string()));
+        assert_eq!(values_equal(("+ff", 16), ("255", 10)), Ok(true));
+        assert_eq!(
+            convert_to_bases(0, &[2], "+0b11"),
+            Ok(vec!["11".to_string()])
+        );
+        assert_eq!(convert_negabase((10, -2), "+6"), Ok("11010".to_string()));
+        // only one sign is allowed
+        assert_eq!(
+            convert_value((10, 16), "+-5"),
+            Err(ConvertError::InvalidDigit {
+                ch: '-',
+                index: 1,
+                base: 10
+            })
+        );
+    }
+
+    #[test]
+    fn convert_value_whitespace_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((16, 10), " ff ", "255"),
+            ((16, 10), "ff\n", "255"),
+            ((16, 10), "ff\r\n", "255"),
+            ((10, 2), "\t-5 \n", "-101"),
+        ];
+
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1).unwrap(), v.2);
+            assert_eq!(check_digits(v.1, v.0 .0), Ok(()));
+        }
+        let inner = || ConvertError::InvalidDigit {
+            ch: '\n',
+            index: 2,
+            base: 16,
+        };
+        assert_eq!(convert_value((16, 10), "ff\nff"), Err(inner()));
+        assert_eq!(check_digits("ff\nff", 16), Err(inner()));
+        assert_eq!(convert_value((16, 10), " \n"), Err(ConvertError::Empty));
+        assert_eq!(
+            check_digits(" g", 16),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 1,
+                base: 16
+            })
+        );
+    }
+
+    #[test]
+    fn same_base_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((16, 16), "00FF", "ff"),
+            ((2, 2), "0b101", "101"),
+            ((10, 10), "-0042", "-42"),
+            ((10, 10), "000", "0"),
+            ((62, 62), "00Zz", "Zz"),
+            ((8, 8), "0o17.40", "17.4"),
+        ];
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1), Ok(v.2.to_string()));
+        }
+
+        // a value far past a u128 is only reformatted, never parsed
+        let huge: String = "F".repeat(100);
+        assert_eq!(convert_value((16, 16), &huge), Ok("f".repeat(100)));
+        assert_eq!(
+            convert_value((16, 16), "0fg"),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 2,
+                base: 16
+            })
+        );
+        assert_eq!(
+            convert_value((10, 63), "1g"),
+            Err(ConvertError::InvalidBase(63))
+        );
+    }
+
+    #[test]
+    fn convert_integer_test() {
+        let vals: Vec<(u128, usize, &str)> = vec![
+            (255, 10, "255"),
+            (255, 16, "ff"),
+            (255, 2, "11111111"),
+            (255, 8, "377"),
+            (0, 62, "0"),
+            (3843, 62, "ZZ"),
+            (u128::MAX, 10, "340282366920938463463374607431768211455"),
+        ];
+        for v in vals {
+            assert_eq!(convert_integer(v.0, v.1), v.2);
+        }
+        assert_eq!(convert_integer(u128::MAX, 2), "1".repeat(128));
+        // every base renders what `base_to_dec` reads back
+        for base in 2..=MAX_BASE {
+            for v in [0, 1, 61, 62, 1 << 64, u128::MAX] {
+                assert_eq!(base_to_dec(&convert_integer(v, base), base), Ok(v));
+            }
+        }
+    }
+
+    #[test]
+    fn convert_value_frac_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((10, 2), "10.5", "1010.1"),
+            ((16, 10), "ff.8", "255.5"),
+            ((10, 16), "-0.25", "-0.4"),
+            ((2, 10), "0.001", "0.125"),
+            ((16, 16), "1.0", "1"),
+            // 1/10 repeats `0022` forever in base 3, truncated to MAX_FRAC_DIGITS
+            ((10, 3), "0.1", "0.00220022002200220022002200220022"),
+        ];
+
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1).unwrap(), v.2);
+        }
+    }
+
+    #[test]
+    fn convert_value_cased_test() {
+        let vals: Vec<((usize, usize), &str, OutputCase, &str)> = vec![
+            ((10, 16), "255", OutputCase::Lower, "ff"),
+            ((10, 16), "255", OutputCase::Upper, "FF"),
+            ((10, 16), "-2748.75", OutputCase::Upper, "-ABC.C"),
+            ((10, 16), "4660", OutputCase::Upper, "1234"),
+            ((10, 2), "5", OutputCase::Upper, "101"),
+            ((16, 36), "ff", OutputCase::Upper, "73"),
+            ((16, 10), "FF", OutputCase::Upper, "255"),
+            ((10, 62), "3843", OutputCase::Upper, "ZZ"),
+            ((10, 62), "2231", OutputCase::Upper, "zZ"),
+        ];
+
+        for v in vals {
+            assert_eq!(convert_value_cased(v.0, v.1, v.2).unwrap(), v.3);
+        }
+        assert_eq!(OutputCase::default(), OutputCase::Lower);
+    }
+
+    #[test]
+    fn apply_case_test() {
+        let vals: Vec<(&str, usize, OutputCase, &str)> = vec![
+            ("ff", 16, OutputCase::Upper, "FF"),
+            ("ff", 16, OutputCase::Lower, "ff"),
+            ("-abc.c", 16, OutputCase::Upper, "-ABC.C"),
+            ("zz", 36, OutputCase::Upper, "ZZ"),
+            ("zZ", 62, OutputCase::Upper, "zZ"),
+        ];
+        for v in vals {
+            assert_eq!(apply_case(v.0.to_string(), v.1, v.2), v.3);
+        }
+    }
+
+    #[test]
+    fn convert_value_prefixed_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((10, 16), "255", "0xff"),
+            ((10, 8), "8", "0o10"),
+            ((10, 2), "5", "0b101"),
+            ((10, 16), "-255", "-0xff"),
+            ((16, 10), "ff", "255"),
+            ((10, 36), "35", "z"),
+        ];
+
+        for v in vals {
+            assert_eq!(convert_value_prefixed(v.0, v.1).unwrap(), v.2);
+        }
+    }
+
+    #[test]
+    fn empty_radix_side_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((16, 10), "ff.", "255"),
+            ((16, 10), ".8", "0.5"),
+            ((16, 16), ".8", "0.8"),
+            ((16, 10), "-.8", "-0.5"),
+            ((16, 10), "0x.8", "0.5"),
+            ((10, 2), "6.", "110"),
+        ];
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1), Ok(v.2.to_string()));
+        }
+        assert_eq!(
+            convert_value((16, 10), "ff."),
+            convert_value((16, 10), "ff")
+        );
+        assert_eq!(
+            convert_value((16, 10), ".8"),
+            convert_value((16, 10), "0.8")
+        );
+
+        assert_eq!(convert_value((16, 10), "."), Err(ConvertError::Empty));
+        assert_eq!(convert_value((16, 10), "-."), Err(ConvertError::Empty));
+        assert_eq!(
+            convert_value((16, 10), ".g"),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 1,
+                base: 16
+            })
+        );
+    }
+
+    #[test]
+    fn scientific_notation_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((10, 16), "1e3", "3e8"),
+            ((10, 16), "2.5e2", "fa"),
+            ((10, 10), "2.50E1", "25"),
+            ((10, 10), "1500e-2", "15"),
+            ((10, 2), "-1e1", "-1010"),
+            ((10, 10), ".5e1", "5"),
+            ((10, 10), "1_000e+0", "1000"),
+            ((0, 16), "1e3", "3e8"),
+            ((10, 10), "0e-5", "0"),
+        ];
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1), Ok(v.2.to_string()));
+        }
+
+        let errs: Vec<(&str, ConvertError)> = vec![
+            ("2.5e0", ConvertError::NotWhole("2.5e0".to_string())),
+            ("1e-3", ConvertError::NotWhole("1e-3".to_string())),
+            (
+                "1e99999",
+                ConvertError::Overflow {
+                    input: "1e99999".to_string(),
+                    base: 10,
+                },
+            ),
+            // a malformed exponent leaves the `e` as a bad digit
+            (
+                "1e",
+                ConvertError::InvalidDigit {
+                    ch: 'e',
+                    index: 1,
+                    base: 10,
+                },
+            ),
+            (
+                "1x5e3",
+                ConvertError::InvalidDigit {
+                    ch: 'x',
+                    index: 1,
+                    base: 10,
+                },
+            ),
+        ];
+        for e in errs {
+            assert_eq!(convert_value((10, 16), e.0), Err(e.1));
+        }
+        // only decimal inputs have an exponent
+        assert_eq!(convert_value((16, 10), "1e3"), Ok("483".to_string()));
+    }
+
+    #[test]
+    fn separator_sign_fraction_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((16, 16), "-de_ad.be_ef", "-dead.beef"),
+            ((16, 10), "-de_ad.be_ef", "-57005.7458343505859375"),
+            ((16, 2), " -0x_f_f.8_0 ", "-11111111.1"),
+            ((10, 10), "+1_000.000_5", "1000.0005"),
+            ((10, 16), "-_1'0", "-a"),
+        ];
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1), Ok(v.2.to_string()));
+            assert_eq!(check_digits(v.1, v.0 .0), Ok(()));
+        }
+
+        let misplaced = |ch: char, index: usize| ConvertError::MisplacedSeparator { ch, index };
+        let vals: Vec<(&str, char, usize)> = vec![
+            ("-de_.adbe", '_', 3),
+            ("-dead._be", '_', 6),
+            ("de ._ad", ' ', 2),
+            ("  1'.5", '\'', 3),
+        ];
+        for v in vals {
+            assert_eq!(convert_value((16, 10), v.0), Err(misplaced(v.1, v.2)));
+            assert_eq!(check_digits(v.0, 16), Err(misplaced(v.1, v.2)));
+        }
+        // a separator before the sign does not hide it
+        assert_eq!(
+            convert_value((16, 10), "_-ff"),
+            Err(ConvertError::InvalidDigit {
+                ch: '-',
+                index: 0,
+                base: 16
+            })
+        );
+        assert_eq!(
+            misplaced('_', 3).to_string(),
+            "separator `_` at index 3 is next to the radix point"
+        );
+    }
+
+    #[test]
+    fn equivalent_width_test() {
+        let vals: Vec<(usize, (usize, usize), usize)> = vec![
+            (4, (16, 2), 16),
+            (4, (16, 10), 5),
+            (16, (2, 16), 4),
+            (3, (10, 16), 3),
+            (2, (10, 2), 7),
+            (4, (16, 16), 4),
+            (0, (16, 2), 0),
+            // far past a u128
+            (100, (16, 2), 400),
+        ];
+        for v in vals {
+            assert_eq!(equivalent_width(v.0, v.1 .0, v.1 .1), Ok(v.2));
+        }
+        // every width-digit value fits in that many digits, and the largest needs them all
+        for width in 1..6 {
+            for (from, to) in [(10, 2), (16, 10), (36, 7)] {
+                let top: u128 = (from as u128).pow(width as u32) - 1;
+                assert_eq!(
+                    equivalent_width(width, from, to),
+                    Ok(dec_to_base(top, to).unwrap().len())
+                );
+            }
+        }
+        assert_eq!(
+            equivalent_width(4, 16, 63),
+            Err(ConvertError::InvalidBase(63))
+        );
+        assert_eq!(equivalent_width(4, 1, 2), Err(ConvertError::InvalidBase(1)));
+    }
+
+    #[test]
+    fn check_width_test() {
+        let vals: Vec<(&str, usize, usize)> = vec![
+            ("00ff", 16, 4),
+            ("-0x00ff", 16, 4),
+            ("+0b0101", 2, 4),
+            ("1_000", 10, 4),
+            (" 12.50 ", 10, 4),
+            ("0d0042", 10, 4),
+        ];
+        for v in vals {
+            assert_eq!(check_width(v.0, v.1, v.2), Ok(()));
+        }
+        let mismatch = |input: &str, expected: usize, found: usize| ConvertError::WidthMismatch {
+            input: input.to_string(),
+            expected,
+            found,
+        };
+        assert_eq!(check_width("ff", 16, 4), Err(mismatch("ff", 4, 2)));
+        assert_eq!(
+            check_width("0x0ffff", 16, 4),
+            Err(mismatch("0x0ffff", 4, 5))
+        );
+        // a prefix of another base counts as digits
+        assert_eq!(check_width("0x1", 10, 1), Err(mismatch("0x1", 1, 3)));
+        assert_eq!(
+            mismatch("ff", 4, 2).to_string(),
+            "`ff` has 2 digits, but exactly 4 are required"
+        );
+    }
+
+    #[test]
+    fn convert_value_unsigned_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((10, 16), "255", "ff"),
+            ((10, 2), "+5", "101"),
+            ((16, 10), " ff ", "255"),
+            ((10, 16), "0", "0"),
+        ];
+        for v in vals {
+            assert_eq!(convert_value_unsigned(v.0, v.1), Ok(v.2.to_string()));
+        }
+        assert_eq!(
+            convert_value_unsigned((10, 2), "-5"),
+            Err(ConvertError::Negative("-5".to_string()))
+        );
+        assert_eq!(
+            convert_value_unsigned((16, 10), " -ff"),
+            Err(ConvertError::Negative(" -ff".to_string()))
+        );
+        // the default stays permissive
+        assert_eq!(convert_value((10, 2), "-5"), Ok("-101".to_string()));
+    }
+
+    #[test]
+    fn pad_digits_test() {
+        let vals: Vec<(&str, usize, &str)> = vec![
+            ("5", 2, "05"),
+            ("5", 4, "0005"),
+            ("ff", 2, "ff"),
+            ("12345", 2, "12345"),
+            ("-5", 4, "-0005"),
+            ("0xf", 4, "0x000f"),
+            ("-0b1", 3, "-0b001"),
+            ("1.5", 3, "001.5"),
+            ("7", 0, "7"),
+        ];
+
+        for v in vals {
+            assert_eq!(pad_digits(v.0, v.1), v.2);
+        }
+    }
+
+    #[test]
+    fn convert_to_bases_test() {
+        let vals: Vec<(usize, &str, Vec<&str>)> = vec![
+            (10, "255", vec!["11111111", "377", "255", "ff"]),
+            (16, "-ff", vec!["-11111111", "-377", "-255", "-ff"]),
+            (0, "0b1010", vec!["1010", "12", "10", "a"]),
+            (2, "-0", vec!["0", "0", "0", "0"]),
+        ];
+        for v in vals {
+            assert_eq!(
+                convert_to_bases(v.0, &[2, 8, 10, 16], v.1),
+                Ok(v.2.iter().map(|s| s.to_string()).collect())
+            );
+        }
+        assert_eq!(convert_to_bases(10, &[], "5"), Ok(vec![]));
+        assert_eq!(
+            convert_to_bases(10, &[2, 63], "5"),
+            Err(ConvertError::InvalidBase(63))
+        );
+        assert_eq!(
+            convert_to_bases(8, &[2], "8"),
+            Err(ConvertError::InvalidDigit {
+                ch: '8',
+                index: 0,
+                base: 8
+            })
+        );
+    }
+
+    #[test]
+    fn format_with_subscript_base_test() {
+        let vals: Vec<(&str, usize, &str)> = vec![
+            ("11111111", 2, "11111111₂"),
+            ("255", 10, "255₁₀"),
+            ("ff", 16, "ff₁₆"),
+            ("-Zz", 62, "-Zz₆₂"),
+        ];
+        for v in vals {
+            assert_eq!(format_with_subscript_base(v.0, v.1), v.2);
+        }
+    }
+
+    #[test]
+    fn group_digits_test() {
+        let vals: Vec<(&str, usize, char, &str)> = vec![
+            ("11111111", 4, '_', "1111_1111"),
+            ("1111111", 4, '_', "111_1111"),
+            ("111111111", 4, '_', "1_1111_1111"),
+            ("ffff", 2, '_', "ff_ff"),
+            ("fff", 2, ' ', "f ff"),
+            ("ff", 4, '_', "ff"),
+            ("", 4, '_', ""),
+            ("-1234567", 3, ',', "-1,234,567"),
+            ("-1010.1011", 2, '_', "-10_10.1011"),
+            ("1234", 0, '_', "1234"),
+        ];
+
+        for v in vals {
+            assert_eq!(group_digits(v.0, v.1, v.2), v.3);
+        }
+    }
+
+    #[test]
+    fn from_twos_complement_test() {
+        let vals: Vec<(&str, usize, u32, i128)> = vec![
+            ("ffffffff", 16, 32, -1),
+            ("80000000", 16, 32, i32::MIN as i128),
+            ("7fffffff", 16, 32, i32::MAX as i128),
+            ("ff", 16, 8, -1),
+            ("0000ff", 16, 16, 255),
+            ("11111110", 2, 8, -2),
+            ("1", 2, 1, -1),
+            ("0", 2, 1, 0),
+        ];
+        for v in vals {
+            assert_eq!(from_twos_complement(v.0, v.1, v.2), Ok(v.3));
+        }
+        // round trips every value of a width through its pattern
+        for value in -128..128 {
+            let pattern: String = to_twos_complement(value, 8, 2).unwrap();
+            assert_eq!(from_twos_complement(&pattern, 2, 8), Ok(value));
+        }
+        let max: String = u128::MAX.to_string();
+        assert_eq!(from_twos_complement(&max, 10, 128), Ok(-1));
+
+        let vals: Vec<(&str, u32)> = vec![("100", 8), ("1", 0), ("1", 129)];
+        for v in vals {
+            assert_eq!(
+                from_twos_complement(v.0, 16, v.1),
+                Err(ConvertError::Overflow {
+                    input: v.0.to_string(),
+                    base: 16
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn reduce_mod_bits_test() {
+        let vals: Vec<(&str, usize, u32, u128)> = vec![
+            ("256", 10, 8, 0),
+            ("511", 10, 8, 255),
+            ("ff", 16, 4, 15),
+            ("-1", 10, 8, 255),
+            ("-256", 10, 8, 0),
+            ("12345", 10, 0, 0),
+            ("12345", 10, 128, 12345),
+            ("-1", 10, 128, u128::MAX),
+            ("1_0000_0001", 2, 8, 1),
+        ];
+        for v in vals {
+            assert_eq!(reduce_mod_bits(v.0, v.1, v.2), Ok(v.3));
+        }
+        assert_eq!(
+            reduce_mod_bits("1g", 16, 8),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 1,
+                base: 16
+            })
+        );
+    }
+
+    #[test]
+    fn saturate_test() {
+        let too_large: String = "f".repeat(40);
+        let vals: Vec<(&str, usize, u128, u128)> = vec![
+            // in range
+            ("200", 10, 255, 200),
+            ("0", 10, 255, 0),
+            ("7f", 16, 255, 127),
+            // at the limit
+            ("255", 10, 255, 255),
+            ("ff", 16, 255, 255),
+            // over the limit
+            ("500", 10, 255, 255),
+            ("1_0000_0000", 2, 255, 255),
+            (&too_large, 16, 255, 255),
+            ("-5", 10, 255, 0),
+            ("-1", 10, 0, 0),
+            ("12345", 10, u128::MAX, 12345),
+        ];
+        for v in vals {
+            assert_eq!(saturate(v.0, v.1, v.2), Ok(v.3));
+        }
+        assert_eq!(saturate(&format!("-{}", too_large), 16, 255), Ok(0));
+        assert_eq!(
+            saturate("1g", 16, 255),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 1,
+                base: 16
+            })
+        );
+        // a bad digit past the point of overflow is still found
+        assert_eq!(
+            saturate(&format!("{}g", too_large), 16, 255),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 40,
+                base: 16
+            })
+        );
+    }
+
+    #[test]
+    fn valid_bases_test() {
+        let vals: Vec<(&str, Vec<usize>)> = vec![
+            ("123", (4..=36).collect()),
+            ("ff", (16..=36).collect()),
+            ("FF", (16..=36).collect()),
+            ("0", (2..=36).collect()),
+            ("z_z", vec![36]),
+            (" 101 ", (2..=36).collect()),
+            ("-1", vec![]),
+            ("1.5", vec![]),
+        ];
+        for v in vals {
+            assert_eq!(valid_bases(v.0), v.1);
+        }
+    }
+
+    #[test]
+    fn radix_complement_test() {
+        let vals: Vec<(u128, usize, usize, &str)> = vec![
+            (123, 10, 5, "99876"),
+            (0x123, 16, 5, "ffedc"),
+            (0, 10, 3, "999"),
+            (999, 10, 3, "000"),
+            (0b101, 2, 4, "1010"),
+            (1, 62, 2, "ZY"),
+        ];
+        for v in vals {
+            assert_eq!(radix_complement(v.0, v.1, v.2), Ok(v.3.to_string()));
+            // complementing twice gives back the value, padded to the width
+            let back: u128 = base_to_dec(v.3, v.1).unwrap();
+            let again: String = radix_complement(back, v.1, v.2).unwrap();
+            assert_eq!(base_to_dec(&again, v.1), Ok(v.0));
+        }
+        let vals: Vec<(u128, usize, usize)> = vec![(1000, 10, 3), (0, 10, 0), (256, 16, 2)];
+        for v in vals {
+            assert_eq!(
+                radix_complement(v.0, v.1, v.2),
+                Err(ConvertError::Overflow {
+                    input: v.0.to_string(),
+                    base: v.1
+                })
+            );
+        }
+        assert_eq!(radix_complement(1, 1, 4), Err(ConvertError::InvalidBase(1)));
+    }
+
+    #[test]
+    fn to_twos_complement_test() {
+        let vals: Vec<(i128, u32, usize, &str)> = vec![
+            (-1, 8, 16, "ff"),
+            (-128, 8, 16, "80"),
+            (127, 8, 16, "7f"),
+            (5, 8, 2, "101"),
+            (-5, 8, 2, "11111011"),
+            (-1, 32, 16, "ffffffff"),
+            (-1, 128, 16, "ffffffffffffffffffffffffffffffff"),
+            (i128::MIN, 128, 16, "80000000000000000000000000000000"),
+            (-1, 1, 10, "1"),
+        ];
+
+        for v in vals {
+            assert_eq!(to_twos_complement(v.0, v.1, v.2).unwrap(), v.3);
+        }
+
+        for (value, bits) in [(128, 8), (-129, 8), (1, 1), (1, 0), (1, 129)] {
+            assert_eq!(
+                to_twos_complement(value, bits, 16),
+                Err(ConvertError::OutOfRange { value, bits })
+            );
+        }
+    }
+
+    #[test]
+    fn convert_value_err_test() {
+        let vals: Vec<((usize, usize), &str, ConvertError)> = vec![
+            ((10, 16), "", ConvertError::Empty),
+            (
+                (16, 10),
+                "fg",
+                ConvertError::InvalidDigit {
+                    ch: 'g',
+                    index: 1,
+                    base: 16,
+                },
+            ),
+            (
+                (2, 10),
+                "2",
+                ConvertError::InvalidDigit {
+                    ch: '2',
+                    index: 0,
+                    base: 2,
+                },
+            ),
+            ((10, 63), "10", ConvertError::InvalidBase(63)),
+            ((99, 2), "1", ConvertError::InvalidBase(99)),
+            (
+                (1, 10),
+                "0",
+                ConvertError::InvalidDigit {
+                    ch: '0',
+                    index: 0,
+                    base: 1,
+                },
+            ),
+            (
+                (36, 10),
+                "Z!",
+                ConvertError::InvalidDigit {
+                    ch: '!',
+                    index: 1,
+                    base: 36,
+                },
+            ),
+        ];
+
+        for v in vals {
+            assert_eq!(convert_value(v.0, v.1), Err(v.2));
+        }
+
+        // multibyte chars must not be sliced into
+        assert_eq!(
+            base_to_dec("é1", 16),
+            Err(ConvertError::InvalidDigit {
+                ch: 'é',
+                index: 0,
+                base: 16
+            })
+        );
+        assert_eq!(
+            base_to_dec("0é", 16),
+            Err(ConvertError::InvalidDigit {
+                ch: 'é',
+                index: 1,
+                base: 16
+            })
+        );
+        assert_eq!(
+            base_to_dec("é", 16),
+            Err(ConvertError::InvalidDigit {
+                ch: 'é',
+                index: 0,
+                base: 16
+            })
+        );
+        assert_eq!(
+            base_to_dec("zzzzzzzzzzzzzzzzzzzzzzzzzzzzzz", 36),
+            Err(ConvertError::Overflow {
+                input: "zzzzzzzzzzzzzzzzzzzzzzzzzzzzzz".to_string(),
+                base: 36
+            })
+        );
+    }
+
+    #[test]
+    fn zero_test() {
+        let zeros: Vec<&str> = vec!["0", "-0", "00", "-00", "0_0", "0.0", "-0.000"];
+        let bases: Vec<usize> = vec![2, 8, 10, 16, 36, 62];
+        for z in zeros {
+            for from in &bases {
+                for to in &bases {
+                    assert_eq!(convert_value((*from, *to), z), Ok("0".to_string()));
+                }
+            }
+            assert_eq!(convert_value((0, 16), z), Ok("0".to_string()));
+        }
+        // prefixed zeros in their own base
+        let prefixed: Vec<(&str, usize)> =
+            vec![("0x0", 16), ("-0x0", 16), ("0o0", 8), ("-0b00", 2)];
+        for p in prefixed {
+            for to in &bases {
+                assert_eq!(convert_value((p.1, *to), p.0), Ok("0".to_string()));
+                assert_eq!(convert_value((0, *to), p.0), Ok("0".to_string()));
+            }
+        }
+        assert_eq!(dec_to_base(0, 2), Ok("0".to_string()));
+        assert_eq!(base_to_dec("0", 2), Ok(0));
+        assert_eq!(base_to_dec("000", 62), Ok(0));
+        // a sign is kept for nonzero fractions
+        assert_eq!(convert_value((10, 2), "-0.5"), Ok("-0.1".to_string()));
+    }
+
+    #[test]
+    fn invalid_digit_index_test() {
+        // a bad digit at every position of a longer value is reported where it sits
+        let digits: String = "0123456789abcdef".repeat(2);
+        for index in 0..digits.len() {
+            let mut bad: Vec<char> = digits.chars().collect();
+            bad[index] = 'g';
+            let bad: String = bad.into_iter().collect();
+            let err = || ConvertError::InvalidDigit {
+                ch: 'g',
+                index,
+                base: 16,
+            };
+            assert_eq!(base_to_dec(&bad, 16), Err(err()));
+            assert_eq!(check_digits(&bad, 16), Err(err()));
+            assert_eq!(convert_value((16, 10), &bad), Err(err()));
+        }
+        // a sign and prefix count toward the index
+        assert_eq!(
+            convert_value((16, 2), "-0xffffz"),
+            Err(ConvertError::InvalidDigit {
+                ch: 'z',
+                index: 7,
+                base: 16
+            })
+        );
+    }
+
+    #[test]
+    fn empty_value_test() {
+        for bases in [(10, 16), (16, 10), (2, 36), (0, 10)] {
+            assert_eq!(convert_value(bases, ""), Err(ConvertError::Empty));
+            assert_eq!(convert_value(bases, "-"), Err(ConvertError::Empty));
+        }
+        assert_eq!(
+            convert_value_prefixed((10, 16), ""),
+            Err(ConvertError::Empty)
+        );
+        assert_eq!(base_to_dec("", 16), Err(ConvertError::Empty));
+        assert_eq!(ConvertError::Empty.to_string(), "value is empty");
+    }
+
+    #[test]
+    fn overflow_test() {
+        // one past usize::MAX fits the u128 accumulator, one past u128::MAX does not
+        let past_usize: String = (usize::MAX as u128 + 1).to_string();
+        assert_eq!(
+            base_to_dec(&past_usize, 10).unwrap(),
+            usize::MAX as u128 + 1
+        );
+
+        let max: String = u128::MAX.to_string();
+        assert_eq!(base_to_dec(&max, 10).unwrap(), u128::MAX);
+
+        let past_max: &str = "340282366920938463463374607431768211456";
+        assert_eq!(
+            base_to_dec(past_max, 10),
+            Err(ConvertError::Overflow {
+                input: past_max.to_string(),
+                base: 10
+            })
+        );
+        assert_eq!(
+            base_to_dec("0x100000000000000000000000000000000", 16),
+            Err(ConvertError::Overflow {
+                input: "0x100000000000000000000000000000000".to_string(),
+                base: 16
+            })
+        );
+        assert_eq!(
+            base_to_frac("0000000000000000000000000000000001", 16),
+            Err(ConvertError::Overflow {
+                input: "0000000000000000000000000000000001".to_string(),
+                base: 16
+            })
+        );
+    }
+
+    #[test]
+    fn convert_value_with_alphabet_test() {
+        let hex: &str = "ABCDEFGHIJKLMNOP";
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((16, 16), "PP", "PP"),
+            ((16, 10), "PP", "CFF"),
+            ((10, 16), "CFF", "PP"),
+            ((16, 2), "K", "BABA"),
+            ((16, 16), "A", "A"),
+            ((16, 16), "AAB", "B"),
+        ];
+
+        for v in vals {
+            assert_eq!(convert_value_with_alphabet(v.0, v.1, hex).unwrap(), v.2);
+        }
+
+        assert_eq!(dec_to_base_with_alphabet(255, 16, hex).unwrap(), "PP");
+        assert_eq!(base_to_dec_with_alphabet("PP", 16, hex).unwrap(), 255);
+        assert_eq!(
+            convert_value_with_alphabet((10, 16), "Z", hex),
+            Err(ConvertError::InvalidDigit {
+                ch: 'Z',
+                index: 0,
+                base: 10
+            })
+        );
+        assert_eq!(
+            convert_value_with_alphabet((10, 16), "K", hex),
+            Err(ConvertError::InvalidDigit {
+                ch: 'K',
+                index: 0,
+                base: 10
+            })
+        );
+        assert_eq!(
+            convert_value_with_alphabet((16, 17), "B", hex),
+            Err(ConvertError::InvalidBase(17))
+        );
+        assert_eq!(
+            convert_value_with_alphabet((2, 2), "B", "ABCA"),
+            Err(ConvertError::DuplicateSymbol('A'))
+        );
+    }
+
+    #[test]
+    fn transcode_test() {
+        let dna: &str = "ACGT";
+        let vals: Vec<(&str, &str, &str, &str)> = vec![
+            // equal lengths map symbol for symbol
+            ("ACGT", dna, "0123", "123"),
+            ("GATTACA", dna, "0123", "2033010"),
+            ("2033010", "0123", dna, "GATTACA"),
+            // differing lengths change the base
+            ("CA", dna, "0123456789", "4"),
+            ("TTTT", dna, "0123456789", "255"),
+            ("TTTT", dna, "0123456789abcdef", "ff"),
+            ("255", "0123456789", dna, "TTTT"),
+            ("A", dna, "01", "0"),
+        ];
+        for v in vals {
+            assert_eq!(transcode(v.0, v.1, v.2), Ok(v.3.to_string()));
+        }
+
+        assert_eq!(
+            transcode("ACGU", dna, "0123"),
+            Err(ConvertError::InvalidDigit {
+                ch: 'U',
+                index: 3,
+                base: 4
+            })
+        );
+        assert_eq!(transcode("", dna, "0123"), Err(ConvertError::Empty));
+        assert_eq!(
+            transcode("A", "A", "0123"),
+            Err(ConvertError::InvalidBase(1))
+        );
+        assert_eq!(
+            transcode("A", dna, "0120"),
+            Err(ConvertError::DuplicateSymbol('0'))
+        );
+    }
+
+    // round trips random values between random bases 2-36, writing the input with
+    // leading zeros and random letter case, which must both drop out of the result
+    proptest::proptest! {
+        #[test]
+        fn round_trip_proptest(
+            value: u128,
+            from in 2usize..=36,
+            to in 2usize..=36,
+            zeros in 0usize..4,
+            upper: bool,
+        ) {
+            let canonical: String = dec_to_base(value, from).unwrap();
+            let mut input: String = "0".repeat(zeros) + &canonical;
+            if upper {
+                input = input.to_ascii_uppercase();
+            }
+            // a zero then x, o or b reads as another base's prefix
+            proptest::prop_assume!(!["0x", "0o", "0b"].iter().any(|p| input.starts_with(p)));
+            proptest::prop_assert_eq!(canonicalize(&input, from), Ok(canonical.clone()));
+
+            let converted: String = convert_value((from, to), &input).unwrap();
+            proptest::prop_assert_eq!(convert_value((to, from), &converted), Ok(canonical));
+            proptest::prop_assert_eq!(convert_value((to, 10), &converted), Ok(value.to_string()));
+        }
+
+        #[test]
+        fn signed_round_trip_proptest(value: i128, from in 2usize..=36, to in 2usize..=36) {
+            let input: String = convert_value((10, from), &value.to_string()).unwrap();
+            let converted: String = convert_value((from, to), &input).unwrap();
+            proptest::prop_assert_eq!(convert_value((to, from), &converted), Ok(input));
+        }
+    }
+}
+
+/*
+    TODO:
+    - check all valid chars
+*/
@@ -1,235 +1,2343 @@
 /*
     This program converts between number bases
-    It takes three arguments:
-        <inbase:u8> <outbase:u8> <value:String>
-    It returns the converted value with specified base
+    It takes three or more arguments:
+        <inbase:u8> <outbase:u8> <value:String>...
+    It returns each converted value with specified base, one per line
+    Its other modes and options are listed in USAGE, printed when the arguments are wrong
 */
 
+use converter::{
+    apply_case, base_to_dec, base_to_text, canonicalize, char_to_digit, check_digits,
+    check_unsigned, check_width, convert_bases, convert_negabase, convert_to_bases, dec_to_base,
+    dec_to_factoradic, detect_base, digit_count, digit_sum, digit_to_char, digital_root,
+    equivalent_width, explain_base_to_dec, explain_dec_to_base, format_with_subscript_base,
+    from_twos_complement, group_digits, hex_byte_pairs, pad_digits, parse_base_arg, parse_bases,
+    prime_factors, reduce_mod_bits, reverse_digits, run_with, saturate, spell_digits, text_to_base,
+    to_gray, to_source_literal, valid_bases, values_equal, ByteOrder, ConvertCache, ConvertError,
+    OutputCase, SourceLang, DIGIT_SEPARATORS, MAX_BASE,
+};
 use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, IsTerminal, Read, Write};
+use std::str::FromStr;
 
-fn get_args() -> Vec<String> {
-    let args: Vec<String> = env::args().skip(1).collect();
-    check_args(&args);
+const USAGE: &str = "Usage: convert [options] <from_base:u8> <to_base:u8> <value:String>...
+       convert --cmp <base_a:u8> <value_a:String> <base_b:u8> <value_b:String>
+       convert [options] --file <path>
+       convert [options] --to <to_base:u8> <value:String>...
+       convert [options] <from_base:u8> <value:String>... --to <to_base:u8>,<to_base:u8>...
+       convert [options] --table <base:u8> [--start <n>] [--end <n> | --count <n>]
+       convert [options] --csv <base:u8> <base:u8> [--range <start> <end>]
+       convert [options] --in <path> [--out <path>] <from_base:u8> <to_base:u8>
+       convert [options] --repl
+       convert [options] --gray <from_base:u8> <value:String>...
+       convert --factoradic <from_base:u8> <value:String>...
+       convert [options] --text-to-base <base:u8> <text:String>...
+       convert --base-to-text <base:u8> <value:String>...
+       convert --digits <base:u8> <value:String>...
+       convert --digitsum <base:u8> <value:String>...
+       convert --ord <digit:char>
+       convert --chr <value:u8>
+       convert --valid-bases <value:String>
+Bases may be numbers or the names bin, oct, dec and hex
+A <from_base> of 0 detects the base from the value's 0x/0o/0b/0d/0 prefix
+A value written as N#digits, eg. 16#ff, is read in base N whatever the <from_base>
+Negative bases (-2 to -62) convert integers to and from negabase form
+A value of `-` is read from stdin, and given only the two bases, eg. `cat nums.txt | convert 10 16`,
+the values are read from stdin, separated by whitespace or newlines
+Exit codes: 0 success, 1 other failure, 2 usage, 3 invalid base, 4 invalid digit, 5 overflow
+Options:
+    --group <n>  separate the output digits into groups of n with `_`
+    --group-char <c> separate the --group groups with c instead, eg. ff'ff
+    --upper      print letter digits in uppercase
+    --pad <n>    left-pad the output with zeros to at least n digits
+    --json       print each result (or error) as a JSON object
+    --verify     convert each result back and check it matches the value
+    --check-only only validate the digits of each value (or --file line), with a summary
+    --file <p>   convert each `<from_base> <to_base> <value>` line of a file
+    --in <p>     convert each line of a file as one value, failed lines become `# ...` comments
+    --out <p>    write the --in results to a file instead of stdout
+    --explain    show the worked steps of each conversion
+    --signed <n> read each value as an n-bit two's-complement pattern, eg. ff at 8 is -1
+    --mod-bits <n> reduce each value modulo 2^n, keeping it unsigned, eg. 511 at 8 is 255
+    --saturate <max> clamp each value to 0 through max, eg. 500 at 255 is 255 and -5 is 0
+    --unsigned   reject negative values, eg. -5, instead of converting them
+    --width <n>  require exactly n input digits, eg. 00ff at 4, unlike --pad for the output
+    --strict-width pad the output to the width of --width, eg. 00ff from 16 to 2 is 0000000011111111
+    --little-endian read and write digits least significant first, eg. 01 in base 16 is 0x10
+    --reverse    swap <from_base> and <to_base>, for converting the other way
+    --thousands  separate decimal output into thousands with `,`, overriding --group
+    --subscript  append the output base in subscript digits, eg. ff₁₆
+    --factor     follow each result with its prime factors, eg. 255 to 16 is ff = 3 * 5 * 11
+    --spell      spell each output digit as a word, eg. ff -> foxtrot foxtrot
+    --as-literal <lang> print each result as a rust or c integer literal, eg. 0o17 or 017
+    --suffix <s> add a type suffix to each --as-literal literal, eg. 0xffu32 or 0xffUL
+    --bytes <endian> split hex output into big or little endian bytes, eg. 11223344 -> 44 33 22 11
+    --color      print alternating digit groups in two colors, unless piped or NO_COLOR is set
+    --quiet      print no error text, only set the exit code
+    --verbose    also print the parsed bases and decimal value of each input to stderr";
+
+// the exit status of each kind of failure, so scripts can branch on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExitCode {
+    // every value converted (or compared equal, or checked valid)
+    Success = 0,
+    // any other failure, eg. an unreadable file or a failed --verify
+    Failure = 1,
+    // bad arguments or flag values
+    Usage = 2,
+    // a base outside the supported range
+    InvalidBase = 3,
+    // a digit that is not valid in its base
+    InvalidDigit = 4,
+    // a value too large for the conversion or bit width
+    Overflow = 5,
+}
+
+impl ExitCode {
+    fn success_if(ok: bool) -> ExitCode {
+        match ok {
+            true => ExitCode::Success,
+            false => ExitCode::Failure,
+        }
+    }
+}
+
+impl From<&ConvertError> for ExitCode {
+    fn from(e: &ConvertError) -> Self {
+        match e {
+            ConvertError::InvalidBase(_)
+            | ConvertError::InvalidNegativeBase(_)
+            | ConvertError::PrefixBaseMismatch { .. }
+            | ConvertError::InvalidBaseArg(_) => ExitCode::InvalidBase,
+            ConvertError::MissingArgs(_) => ExitCode::Usage,
+            ConvertError::InvalidDigit { .. } | ConvertError::MisplacedSeparator { .. } => {
+                ExitCode::InvalidDigit
+            }
+            ConvertError::Overflow { .. }
+            | ConvertError::OutOfRange { .. }
+            | ConvertError::RomanRange(_) => ExitCode::Overflow,
+            _ => ExitCode::Failure,
+        }
+    }
+}
+
+impl From<ExitCode> for std::process::ExitCode {
+    fn from(code: ExitCode) -> Self {
+        std::process::ExitCode::from(code as u8)
+    }
+}
+
+// the exit code of the first error, or success if there are none
+fn first_error_code<'a>(mut errors: impl Iterator<Item = &'a ConvertError>) -> ExitCode {
+    errors.next().map_or(ExitCode::Success, ExitCode::from)
+}
+
+// end the process early with `code`, for failures found while reading the arguments
+fn exit(code: ExitCode) -> ! {
+    std::process::exit(code as i32)
+}
+
+// how much is written to stderr
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+}
+
+// options given as `--flag [value]` alongside the positional args
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    group: Option<usize>,
+    group_char: Option<char>,
+    case: OutputCase,
+    pad: Option<usize>,
+    json: bool,
+    verify: bool,
+    check_only: bool,
+    cmp: bool,
+    file: Option<String>,
+    input: Option<String>,
+    repl: bool,
+    gray: bool,
+    factoradic: bool,
+    digits: Option<usize>,
+    digitsum: Option<usize>,
+    text_to_base: Option<usize>,
+    base_to_text: Option<usize>,
+    output: Option<String>,
+    explain: bool,
+    to: Option<Vec<isize>>,
+    signed: Option<u32>,
+    mod_bits: Option<u32>,
+    saturate: Option<u128>,
+    unsigned: bool,
+    width: Option<usize>,
+    strict_width: bool,
+    verbosity: Verbosity,
+    subscript: bool,
+    color: bool,
+    spell: bool,
+    as_literal: Option<SourceLang>,
+    suffix: Option<String>,
+    bytes: Option<ByteOrder>,
+    factor: bool,
+    thousands: bool,
+    little_endian: bool,
+    reverse: bool,
+    table: Option<usize>,
+    csv: bool,
+    range: Option<(u128, u128)>,
+    start: Option<u128>,
+    end: Option<u128>,
+    count: Option<u128>,
+    ord: Option<char>,
+    chr: Option<u8>,
+    valid_bases: Option<String>,
+}
+
+fn get_args() -> (Options, Vec<String>) {
+    // an arg that is not valid unicode keeps a U+FFFD in place of each bad sequence,
+    // to be reported as an invalid digit rather than panicking here
+    let raw: Vec<String> = env::args_os()
+        .skip(1)
+        .map(|a| a.to_string_lossy().into_owned())
+        .collect();
+    let (mut opts, args) = parse_flags(raw);
+    opts.color = color_enabled(
+        opts.color,
+        env::var_os("NO_COLOR"),
+        io::stdout().is_terminal(),
+    );
+    // text args are not digits, so they are neither base names nor checked as digits here
+    if opts.text_to_base.is_some() || opts.base_to_text.is_some() {
+        if args.is_empty() {
+            report(&opts, USAGE);
+            exit(ExitCode::Usage);
+        }
+        return (opts, args);
+    }
+    let args: Vec<String> = with_base_names(&opts, args);
+    if opts.cmp {
+        check_cmp_args(&args, &opts);
+        return (opts, args);
+    }
+    if opts.file.is_some()
+        || opts.table.is_some()
+        || opts.ord.is_some()
+        || opts.chr.is_some()
+        || opts.valid_bases.is_some()
+        || opts.repl
+    {
+        return (opts, args);
+    }
+    let args: Vec<String> = with_reversed_bases(&opts, args);
+    if opts.input.is_some() || opts.csv {
+        if args.len() != 2 {
+            report(&opts, USAGE);
+            exit(ExitCode::Usage);
+        }
+        check_bases(&args, &opts);
+        return (opts, args);
+    }
+    let digit_mode: bool = opts.digits.is_some() || opts.digitsum.is_some();
+    if fan_out_bases(&opts).is_some() || opts.gray || opts.factoradic || digit_mode {
+        let mut args: Vec<String> = args;
+        // --digits and --digitsum values have their base detected, like an input base of 0
+        if digit_mode {
+            args.insert(0, String::from("0"));
+        }
+        for v in args.iter_mut().skip(1) {
+            if v == "-" {
+                *v = read_stdin(&opts);
+            }
+        }
+        check_fan_out_args(&args, &opts);
+        return (opts, args);
+    }
+    let mut args: Vec<String> = with_to_base(&opts, args);
+    // only the two bases were given, so the values are piped on stdin
+    if args.len() == 2 && !io::stdin().is_terminal() {
+        match read_values(io::stdin().lock()) {
+            Ok(values) => args.extend(values),
+            Err(e) => {
+                report(
+                    &opts,
+                    &format!("values could not be read from stdin\n{}", e),
+                );
+                exit(ExitCode::Failure);
+            }
+        }
+    }
+    // a value of `-` means read it from stdin instead
+    for v in args.iter_mut().skip(2) {
+        if v == "-" {
+            *v = read_stdin(&opts);
+        }
+    }
+    check_args(&args, &opts);
+    (opts, args)
+}
+
+// replace named bases in the positional args that hold bases in each mode
+fn with_base_names(opts: &Options, args: Vec<String>) -> Vec<String> {
+    let positions: &[usize] = if opts.cmp {
+        &[0, 2]
+    } else if opts.file.is_some() || opts.table.is_some() || opts.to.is_some() {
+        match fan_out_bases(opts) {
+            Some(_) => &[0],
+            None => &[],
+        }
+    } else {
+        &[0, 1]
+    };
+    args.into_iter()
+        .enumerate()
+        .map(|(i, a)| match positions.contains(&i) {
+            true => match parse_base_arg(&a) {
+                Ok(b) => b.to_string(),
+                Err(_) => a,
+            },
+            false => a,
+        })
+        .collect()
+}
+
+// `--reverse` swaps the two leading base args of the two-base forms, eg. `16 10` -> `10 16`
+fn with_reversed_bases(opts: &Options, mut args: Vec<String>) -> Vec<String> {
+    if opts.reverse && opts.to.is_none() && args.len() >= 2 {
+        args.swap(0, 1);
+    }
     args
 }
 
-fn check_args(args: &Vec<String>) {
+// `--to <base>` stands in for both bases, autodetecting the input base (base 0)
+fn with_to_base(opts: &Options, args: Vec<String>) -> Vec<String> {
+    match opts.to.as_deref() {
+        Some([b]) => [String::from("0"), b.to_string()]
+            .into_iter()
+            .chain(args)
+            .collect(),
+        _ => args,
+    }
+}
+
+// `--to` with several bases converts to each of them instead
+fn fan_out_bases(opts: &Options) -> Option<&[isize]> {
+    opts.to.as_deref().filter(|b| b.len() > 1)
+}
+
+// parse a comma separated list of bases, eg. `2,8,16`
+fn base_list(flag: &str, val: String) -> Result<Vec<isize>, String> {
+    val.split(',')
+        .map(|b| parse_base_arg(b.trim()))
+        .collect::<Result<Vec<isize>, ConvertError>>()
+        .map_err(|_| format!("{} is not a valid value for {}", val, flag))
+}
+
+// parse a byte order, big or little, eg. for `--bytes little`
+fn byte_order(flag: &str, val: String) -> Result<ByteOrder, String> {
+    match val.as_str() {
+        "big" | "be" => Ok(ByteOrder::Big),
+        "little" | "le" => Ok(ByteOrder::Little),
+        _ => Err(format!(
+            "{} is not a valid value for {}; use big or little",
+            val, flag
+        )),
+    }
+}
+
+// split the flags out of the raw args, leaving the positional args in order
+fn parse_flags(raw: Vec<String>) -> (Options, Vec<String>) {
+    let mut opts = Options::default();
+    let mut args: Vec<String> = Vec::new();
+    // the first bad flag value is reported once every flag, including --quiet, is known
+    let mut error: Option<String> = None;
+    let mut iter = raw.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--group" => opts.group = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--group-char" => {
+                opts.group_char = keep_error(&mut error, flag_value(&arg, iter.next()))
+            }
+            "--upper" => opts.case = OutputCase::Upper,
+            "--pad" => opts.pad = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--json" => opts.json = true,
+            "--verify" => opts.verify = true,
+            "--check-only" => opts.check_only = true,
+            "--cmp" => opts.cmp = true,
+            "--repl" => opts.repl = true,
+            "--gray" => opts.gray = true,
+            "--factoradic" => opts.factoradic = true,
+            "--digits" => opts.digits = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--digitsum" => opts.digitsum = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--text-to-base" => {
+                opts.text_to_base = keep_error(&mut error, flag_value(&arg, iter.next()))
+            }
+            "--base-to-text" => {
+                opts.base_to_text = keep_error(&mut error, flag_value(&arg, iter.next()))
+            }
+            "--explain" => opts.explain = true,
+            "--to" => {
+                let bases = flag_value(&arg, iter.next()).and_then(|v| base_list(&arg, v));
+                opts.to = keep_error(&mut error, bases)
+            }
+            "--signed" => opts.signed = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--mod-bits" => opts.mod_bits = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--saturate" => opts.saturate = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--unsigned" => opts.unsigned = true,
+            "--width" => opts.width = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--strict-width" => opts.strict_width = true,
+            "--file" => opts.file = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--in" => opts.input = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--out" => opts.output = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--subscript" => opts.subscript = true,
+            "--color" => opts.color = true,
+            "--spell" => opts.spell = true,
+            "--as-literal" => {
+                opts.as_literal = keep_error(&mut error, flag_value(&arg, iter.next()))
+            }
+            "--suffix" => opts.suffix = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--bytes" => {
+                let order = flag_value(&arg, iter.next()).and_then(|v| byte_order(&arg, v));
+                opts.bytes = keep_error(&mut error, order)
+            }
+            "--factor" => opts.factor = true,
+            "--thousands" => opts.thousands = true,
+            "--little-endian" => opts.little_endian = true,
+            "--reverse" => opts.reverse = true,
+            "--table" => opts.table = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--csv" => opts.csv = true,
+            "--range" => {
+                let range = flag_value(&arg, iter.next())
+                    .and_then(|start| Ok((start, flag_value(&arg, iter.next())?)));
+                opts.range = keep_error(&mut error, range)
+            }
+            "--start" => opts.start = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--end" => opts.end = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--count" => opts.count = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--ord" => opts.ord = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--chr" => opts.chr = keep_error(&mut error, flag_value(&arg, iter.next())),
+            "--valid-bases" => {
+                opts.valid_bases = keep_error(&mut error, flag_value(&arg, iter.next()))
+            }
+            "--quiet" => opts.verbosity = Verbosity::Quiet,
+            "--verbose" => opts.verbosity = Verbosity::Verbose,
+            _ => args.push(arg),
+        }
+    }
+    let modes = [
+        ("--signed", opts.signed.is_some()),
+        ("--mod-bits", opts.mod_bits.is_some()),
+        ("--saturate", opts.saturate.is_some()),
+    ];
+    let set: Vec<&str> = modes.iter().filter(|m| m.1).map(|m| m.0).collect();
+    if error.is_none() && set.len() > 1 {
+        error = Some(format!("{} can not be combined", set.join(" and ")));
+    }
+    if let Some(e) = error {
+        report(&opts, &format!("{}\n{}", e, USAGE));
+        exit(ExitCode::Usage);
+    }
+    (opts, args)
+}
+
+// keep the first error seen in `error`, turning a result into an option
+fn keep_error<T>(error: &mut Option<String>, result: Result<T, String>) -> Option<T> {
+    result.map_err(|e| *error = error.take().or(Some(e))).ok()
+}
+
+// print an error or warning, unless --quiet
+fn report(opts: &Options, msg: &str) {
+    if opts.verbosity != Verbosity::Quiet {
+        eprintln!("{}", msg);
+    }
+}
+
+// print a diagnostic, only with --verbose
+fn debug(opts: &Options, msg: &str) {
+    if opts.verbosity == Verbosity::Verbose {
+        eprintln!("{}", msg);
+    }
+}
+
+// parse the value following a flag, failing if it is missing or invalid
+fn flag_value<T: FromStr>(flag: &str, val: Option<String>) -> Result<T, String> {
+    match val.as_deref().map(str::parse::<T>) {
+        Some(Ok(v)) => Ok(v),
+        Some(Err(_)) => Err(format!(
+            "{} is not a valid value for {}",
+            val.unwrap(),
+            flag
+        )),
+        None => Err(format!("{} requires a value", flag)),
+    }
+}
+
+fn read_stdin(opts: &Options) -> String {
+    let mut buf = String::new();
+    if let Err(e) = io::stdin().read_to_string(&mut buf) {
+        report(opts, &format!("value could not be read from stdin\n{}", e));
+        exit(ExitCode::Failure);
+    }
+    buf.trim_end_matches(['\n', '\r']).to_string()
+}
+
+// split the text of `reader` into whitespace or newline separated values
+fn read_values<R: Read>(mut reader: R) -> io::Result<Vec<String>> {
+    let mut buf = String::new();
+    reader.read_to_string(&mut buf)?;
+    Ok(buf.split_whitespace().map(String::from).collect())
+}
+
+fn check_args(args: &[String], opts: &Options) {
     // check arg count and provide usage
-    if args.len() != 3 {
-        eprint!("Usage: convert <from_base:u8> <to_base:u8> <value:String>...\n");
-        std::process::exit(1);
+    if args.len() < 3 {
+        report(opts, USAGE);
+        exit(ExitCode::Usage);
+    }
+    check_bases(args, opts);
+    // bytes are pairs of hex digits, so other output bases have none
+    if opts.bytes.is_some() && args[1] != "16" {
+        report(opts, "--bytes needs an output base of 16");
+        exit(ExitCode::Usage);
+    }
+
+    //ensure every value argument is valid first argument base.
+    //eg. if base1 = 11 then all digits must map below 11, ie. <= 'a' or 'A'
+    //json output and --check-only report bad values per value instead
+    let base: usize = args[0].parse::<isize>().unwrap().unsigned_abs();
+    if base == 0 || opts.json || opts.check_only {
+        return;
+    }
+    for v in &args[2..] {
+        if let Err(e) = check_digits(v, base) {
+            report(opts, &e.to_string());
+            exit(ExitCode::from(&e));
+        }
     }
+}
 
-    // ensure first two args are usize ints between 2 and 36
-    for s in &args[0..2] {
+fn check_bases(args: &[String], opts: &Options) {
+    // the first two args must be the bases `run` accepts, see `parse_bases`
+    if let Err(e) = parse_bases(&args[0], &args[1]) {
+        report(opts, &e.to_string());
+        exit(ExitCode::from(&e));
+    }
+}
+
+// a fan out takes an input base and values, converting to bases 2 to MAX_BASE
+fn check_fan_out_args(args: &[String], opts: &Options) {
+    if args.len() < 2 {
+        report(opts, USAGE);
+        exit(ExitCode::Usage);
+    }
+    let in_base: usize = match args[0].parse::<usize>() {
+        Ok(v) if v == 0 || (2..=MAX_BASE).contains(&v) => v,
+        _ => {
+            report(
+                opts,
+                &format!(
+                    "{} is not a valid base; bases: 2-{} allowed",
+                    args[0], MAX_BASE
+                ),
+            );
+            exit(ExitCode::InvalidBase);
+        }
+    };
+    for b in fan_out_bases(opts).unwrap_or_default() {
+        if !(2..=MAX_BASE as isize).contains(b) {
+            report(
+                opts,
+                &format!("{} is not a valid base; bases: 2-{} allowed", b, MAX_BASE),
+            );
+            exit(ExitCode::InvalidBase);
+        }
+    }
+    if in_base == 0 {
+        return;
+    }
+    for v in &args[1..] {
+        if let Err(e) = check_digits(v, in_base) {
+            report(opts, &e.to_string());
+            exit(ExitCode::from(&e));
+        }
+    }
+}
+
+// --cmp takes exactly two base and value pairs
+fn check_cmp_args(args: &[String], opts: &Options) {
+    if args.len() != 4 {
+        report(opts, USAGE);
+        exit(ExitCode::Usage);
+    }
+    for s in [&args[0], &args[2]] {
         match s.parse::<usize>() {
-            Ok(v) => match v {
-                2..=36 => (),
-                _ => {
-                    eprintln!("{} is not a valid base; bases: 2-36 allowed", v);
-                    std::process::exit(1);
-                }
-            },
-            Err(e) => {
-                eprintln!("{} can not be parsed into a usize; invalid base\n{}", s, e);
-                std::process::exit(1);
+            Ok(v) if (2..=MAX_BASE).contains(&v) => (),
+            _ => {
+                report(
+                    opts,
+                    &format!("{} is not a valid base; bases: 2-{} allowed", s, MAX_BASE),
+                );
+                exit(ExitCode::InvalidBase);
             }
         }
     }
+}
 
-    //ensure third argument is valid first argument base.
-    //eg. if base1 = 11 then all chars <= 'a' etc
-    let max_char: char = char_map(args[0].parse::<u8>().unwrap()).unwrap();
-    for c in args[2].chars() {
-        if c >= max_char {
-            eprintln!("`{}` exceeds the exclusive max value `{}` of base {}", c, max_char, args[0]);
-            std::process::exit(1);
+// apply the output formatting flags to a converted value
+fn format_output(val: String, out_base: isize, opts: &Options) -> String {
+    let mut output: String = val;
+    if let Some(width) = opts.pad {
+        output = pad_digits(&output, width);
+    }
+    let group: Option<(usize, char)> = match (opts.thousands, out_base, opts.group) {
+        (true, 10, _) => Some((3, ',')),
+        (_, _, Some(n)) => Some((n, opts.group_char.unwrap_or('_'))),
+        _ => None,
+    };
+    if let Some((n, sep)) = group {
+        output = group_digits(&output, n, sep);
+        if opts.color {
+            output = color_groups(&output, sep);
         }
     }
+    if opts.subscript {
+        // a negative base gets a subscript minus, U+208B
+        if out_base < 0 {
+            output.push('\u{208b}');
+        }
+        output = format_with_subscript_base(&output, out_base.unsigned_abs());
+    }
+    output
 }
 
-fn char_map(i: u8) -> Option<char> {
-    // assumes no base greater than 36
-    match i {
-        0..=9 => Some((i + 48) as char),
-        10..=36 => Some((i + 87) as char),
-        _ => None,
+// write a converted value as a source literal, padded but not grouped, eg. 0x00ffu32
+fn source_literal(
+    val: String,
+    out_base: isize,
+    lang: SourceLang,
+    opts: &Options,
+) -> Result<String, ConvertError> {
+    let base: usize =
+        usize::try_from(out_base).map_err(|_| ConvertError::InvalidNegativeBase(out_base))?;
+    let digits: String = match opts.pad {
+        Some(width) => pad_digits(&val, width),
+        None => val,
+    };
+    to_source_literal(&digits, base, lang, opts.suffix.as_deref())
+}
+
+// split converted hex into bytes, padded but not grouped, eg. 00 ff little-endian -> ff 00
+fn byte_pairs(val: String, order: ByteOrder, opts: &Options) -> Result<String, ConvertError> {
+    let digits: String = match opts.pad {
+        Some(width) => pad_digits(&val, width),
+        None => val,
+    };
+    hex_byte_pairs(&digits, order)
+}
+
+// ANSI colors for alternate digit groups, cyan and yellow, and the reset after each
+const GROUP_COLORS: [&str; 2] = ["\x1b[36m", "\x1b[33m"];
+const COLOR_RESET: &str = "\x1b[0m";
+
+// --color only applies when stdout is a terminal and NO_COLOR is unset or empty
+fn color_enabled(requested: bool, no_color: Option<std::ffi::OsString>, tty: bool) -> bool {
+    requested && tty && no_color.is_none_or(|v| v.is_empty())
+}
+
+// wrap each `sep` separated group of `s` in the next of the alternating colors
+fn color_groups(s: &str, sep: char) -> String {
+    s.split(sep)
+        .enumerate()
+        .map(|(i, g)| format!("{}{}{}", GROUP_COLORS[i % 2], g, COLOR_RESET))
+        .collect::<Vec<String>>()
+        .join(&sep.to_string())
+}
+
+// rows of a counting table, each number in decimal beside its digits in base
+// the range runs from --start (default 0) to an inclusive --end, or for --count rows (default 16)
+fn table(base: usize, opts: &Options) -> Result<Vec<String>, ConvertError> {
+    let start: u128 = opts.start.unwrap_or(0);
+    let end: u128 = match (opts.end, opts.count) {
+        (Some(e), _) => e,
+        (None, Some(0)) => return Ok(Vec::new()),
+        (None, c) => start.saturating_add(c.unwrap_or(16) - 1),
+    };
+
+    let mut rows: Vec<(String, String)> = Vec::new();
+    for n in start..=end {
+        let digits: String = apply_case(dec_to_base(n, base)?, base, opts.case);
+        rows.push((n.to_string(), format_output(digits, base as isize, opts)));
     }
+
+    // right-align both columns to their widest entry
+    let dec_width: usize = rows.iter().map(|r| r.0.chars().count()).max().unwrap_or(0);
+    let base_width: usize = rows.iter().map(|r| r.1.chars().count()).max().unwrap_or(0);
+    Ok(rows
+        .iter()
+        .map(|(d, b)| format!("{:>dw$}  {:>bw$}", d, b, dw = dec_width, bw = base_width))
+        .collect())
 }
 
-fn map_char(c: char) -> Option<u8> {
-    let c_low: char = c.to_ascii_lowercase();
-    match c_low {
-        '0'..='9' => Some((c_low as u8) - 48u8),
-        'a'..='z' => Some((c_low as u8) - 87u8),
-        _ => None,
+// a lookup table as CSV lines, a header naming each base and then a row per number of the
+// inclusive --range (default 0 to 15) with its digits in each base
+fn csv(bases: (isize, isize), opts: &Options) -> Result<Vec<String>, ConvertError> {
+    let columns: Vec<usize> = [bases.0, bases.1]
+        .into_iter()
+        .map(|b| usize::try_from(b).map_err(|_| ConvertError::InvalidNegativeBase(b)))
+        .collect::<Result<Vec<usize>, ConvertError>>()?;
+    let (start, end): (u128, u128) = opts.range.unwrap_or((0, 15));
+
+    let mut lines: Vec<String> = vec![columns
+        .iter()
+        .map(|b| csv_field(&format!("base {}", b)))
+        .collect::<Vec<String>>()
+        .join(",")];
+    for n in start..=end {
+        let mut fields: Vec<String> = Vec::new();
+        for base in &columns {
+            let digits: String = apply_case(dec_to_base(n, *base)?, *base, opts.case);
+            fields.push(csv_field(&format_output(digits, *base as isize, opts)));
+        }
+        lines.push(fields.join(","));
     }
+    Ok(lines)
 }
 
-fn base_to_dec(in_val: &str, base: usize) -> usize {
-    //handle single char case
-    if in_val.len() == 1 {
-        let first_char: char = in_val.chars().next().unwrap();
-        return map_char(first_char).unwrap().try_into().unwrap();
+// quote a CSV field that holds a comma, quote or line break, doubling its quotes
+fn csv_field(s: &str) -> String {
+    match s.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", s.replace('"', "\"\"")),
+        false => s.to_string(),
     }
+}
 
-    //handle leading 0x chars if present
-    let in_str: String = match &in_val[0..2] {
-        "0x" | "0b" | "0o" => in_val[2..].to_string(),
-        _ => in_val.to_string(),
+// the digit value and code point of the digit char `c`, eg. `a` -> `10 (U+0061)`
+fn ord(c: char) -> Result<String, String> {
+    match char_to_digit(c) {
+        Some(v) => Ok(format!("{} (U+{:04X})", v, u32::from(c))),
+        None => Err(format!("`{}` is not a digit; digits: 0-9, a-z and A-Z", c)),
+    }
+}
+
+// the digit char of the value `n`, eg. 10 -> `a`
+fn chr(n: u8) -> Result<String, String> {
+    match digit_to_char(n) {
+        Some(c) => Ok(c.to_string()),
+        None => Err(format!(
+            "{} is not a digit value; values: 0-{}",
+            n,
+            MAX_BASE - 1
+        )),
+    }
+}
+
+// the bases in which `val` is a legal literal, eg. `ff` -> `16 17 ... 36`
+fn bases_of(val: &str) -> Result<String, String> {
+    let bases: Vec<String> = valid_bases(val).iter().map(usize::to_string).collect();
+    match bases.is_empty() {
+        true => Err(format!("`{}` is not a valid value in any base 2-36", val)),
+        false => Ok(bases.join(" ")),
+    }
+}
+
+// parse the unsigned integer `val` in `in_base`, allowing separators and a prefix
+fn parse_unsigned(in_base: isize, val: &str) -> Result<u128, ConvertError> {
+    let in_base: usize = in_base_of(in_base, val);
+    base_to_dec(&canonicalize(val, in_base)?, in_base)
+}
+
+// the digit sum and digital root of `n` in `base`, eg. 9875 in base 10 -> `29 2`
+fn digit_sum_root(n: u128, base: usize) -> Result<String, ConvertError> {
+    Ok(format!(
+        "{} {}",
+        digit_sum(n, base)?,
+        digital_root(n, base)?
+    ))
+}
+
+// the prime factors of the integer `val` joined by ` * ` in the output base, eg. 255 from
+// 10 to 16 -> `3 * 5 * 11`; a negative value leads with -1, and 0 and 1 stand for themselves
+fn factor(bases: (isize, isize), val: &str, opts: &Options) -> Result<String, ConvertError> {
+    let decimal: String = convert_arg((bases.0, 10), val, opts)?;
+    let (is_neg, magnitude) = match decimal.strip_prefix('-') {
+        Some(m) => (true, m),
+        None => (false, decimal.as_str()),
     };
+    // a fraction has no prime factors, so point at its radix point; any other value that
+    // fails to parse is too large to factor, as with the `bignum` feature
+    let in_base: usize = in_base_of(bases.0, val);
+    let n: u128 = magnitude
+        .parse()
+        .map_err(|_| match val.chars().position(|c| c == '.') {
+            Some(index) if magnitude.contains('.') => ConvertError::InvalidDigit {
+                ch: '.',
+                index,
+                base: in_base,
+            },
+            _ => ConvertError::Overflow {
+                input: val.to_string(),
+                base: in_base,
+            },
+        })?;
 
-    let mut out_val: usize = 0;
-    for (i, c) in in_str.chars().rev().enumerate() {
-        let char_val: usize = map_char(c).unwrap().try_into().unwrap();
-        let pval: usize = usize::pow(base, i.try_into().unwrap());
-        out_val += char_val * pval;
+    let mut factors: Vec<String> = Vec::new();
+    if is_neg {
+        factors.push(convert_bases((10, bases.1), "-1", opts.case)?);
+    }
+    match prime_factors(n).as_slice() {
+        [] => factors.push(convert_bases((10, bases.1), magnitude, opts.case)?),
+        primes => {
+            for p in primes {
+                factors.push(convert_bases((10, bases.1), &p.to_string(), opts.case)?);
+            }
+        }
+    }
+    Ok(factors.join(" * "))
+}
+
+// the Gray code of the unsigned integer `val` in `in_base`, eg. 5 -> `111`
+fn gray(in_base: isize, val: &str) -> Result<String, ConvertError> {
+    parse_unsigned(in_base, val).map(to_gray)
+}
+
+// convert one value to every base, labeling each line with its base
+fn fan_out(
+    in_base: usize,
+    bases: &[isize],
+    val: &str,
+    opts: &Options,
+) -> Result<Vec<String>, ConvertError> {
+    let out_bases: Vec<usize> = bases.iter().map(|b| b.unsigned_abs()).collect();
+    let outputs: Vec<String> = convert_to_bases(in_base, &out_bases, val)?;
+    Ok(out_bases
+        .iter()
+        .zip(outputs)
+        .map(|(b, o)| {
+            let o: String = apply_case(o, *b, opts.case);
+            format!("base {}: {}", b, format_output(o, *b as isize, opts))
+        })
+        .collect())
+}
+
+// read `val` into an integer with `read`, given its input base, before converting the
+// result, eg. as a two's-complement pattern for --signed
+fn convert_read<T: ToString>(
+    bases: (isize, isize),
+    val: &str,
+    case: OutputCase,
+    read: impl Fn(&str, usize) -> Result<T, ConvertError>,
+) -> Result<String, ConvertError> {
+    let value: T = read(val, in_base_of(bases.0, val))?;
+    convert_bases((10, bases.1), &value.to_string(), case)
+}
+
+// convert one value arg as the flags ask, logging its decimal value when verbose
+fn convert_arg(bases: (isize, isize), v: &str, opts: &Options) -> Result<String, ConvertError> {
+    if opts.unsigned {
+        check_unsigned(v)?;
+    }
+    if let Some(width) = opts.width {
+        check_width(v, in_base_of(bases.0, v), width)?;
+    }
+    // --signed, --mod-bits and --saturate read the value into an integer first
+    let convert = |bases: (isize, isize)| match (opts.signed, opts.mod_bits, opts.saturate) {
+        (Some(bits), _, _) => {
+            convert_read(bases, v, opts.case, |v, b| from_twos_complement(v, b, bits))
+        }
+        (_, Some(bits), _) => convert_read(bases, v, opts.case, |v, b| reduce_mod_bits(v, b, bits)),
+        (_, _, Some(max)) => convert_read(bases, v, opts.case, |v, b| saturate(v, b, max)),
+        _ => convert_bases(bases, v, opts.case),
+    };
+    let decimal = convert((bases.0, 10));
+    if let Ok(d) = &decimal {
+        debug(opts, &format!("`{}` is {} in base 10", v, d));
     }
-    out_val
+    let output: String = convert(bases)?;
+    match (opts.width, opts.strict_width) {
+        (Some(width), true) => keep_width(bases, v, output, width),
+        (Some(width), false) => {
+            let in_base: usize = in_base_of(bases.0, v);
+            let canonical = canonicalize(v, in_base);
+            if canonical.is_ok_and(|c| check_width(&c, in_base, width).is_err()) {
+                report(
+                    opts,
+                    &format!(
+                        "warning: the leading zeros of `{}` are dropped; --strict-width keeps them",
+                        v
+                    ),
+                );
+            }
+            Ok(output)
+        }
+        (None, _) => Ok(output),
+    }
+}
+
+// pad the conversion of a `width` digit `val` to as many integer digits as that width
+// takes in the output base, so its leading zeros survive, eg. 00ff from 16 to 2 -> 0000000011111111
+fn keep_width(
+    bases: (isize, isize),
+    val: &str,
+    output: String,
+    width: usize,
+) -> Result<String, ConvertError> {
+    let out_base: usize =
+        usize::try_from(bases.1).map_err(|_| ConvertError::InvalidNegativeBase(bases.1))?;
+    // the fraction digits count toward the width but are not padded
+    let frac_width: usize = val.trim().split_once('.').map_or(0, |(_, f)| {
+        f.chars().filter(|c| !DIGIT_SEPARATORS.contains(c)).count()
+    });
+    let int_width: usize =
+        equivalent_width(width - frac_width, in_base_of(bases.0, val), out_base)?;
+    Ok(pad_digits(&output, int_width))
 }
 
-fn dec_to_base(in_dec: usize, base: usize) -> String {
-    if in_dec == 0 {
-        return "0".to_string();
+// convert `output` back to the input base and compare it with `val`
+// both sides go through the input base so prefixes, separators, case and leading zeros drop out
+fn round_trips(bases: (isize, isize), val: &str, output: &str) -> Result<bool, ConvertError> {
+    let in_base: isize = match bases.0 {
+        0 => detect_base(val.trim_start_matches(['-', '+'])) as isize,
+        b => b,
+    };
+    let back: String = convert_bases((bases.1, in_base), output, OutputCase::Lower)?;
+    let normalized: String = convert_bases((bases.0, in_base), val, OutputCase::Lower)?;
+    Ok(back == normalized)
+}
+
+// how many distinct conversions a --file or --in batch remembers, as values often repeat
+const BATCH_CACHE_SIZE: usize = 4096;
+
+// like `convert_bases`, but answering repeated positive base conversions from `cache`
+fn convert_cached(
+    cache: &mut ConvertCache,
+    bases: (isize, isize),
+    val: &str,
+    case: OutputCase,
+) -> Result<String, ConvertError> {
+    match bases {
+        (0.., 0..) => cache.convert_cased((bases.0 as usize, bases.1 as usize), val, case),
+        _ => convert_negabase(bases, val),
     }
+}
 
-    let mut output = String::new();
-    let mut cur_val = in_dec;
-    while cur_val != 0 {
-        let res = (cur_val / base, cur_val % base);
-        cur_val = res.0;
-        output.insert(0, char_map(res.1.try_into().unwrap()).expect("whoops"))
+// convert each `<inbase> <outbase> <value>` line of text to `value -> result`
+// malformed lines become an error naming their line number, the rest still convert
+fn convert_lines(text: &str, opts: &Options) -> Vec<Result<String, String>> {
+    let mut cache = ConvertCache::new(BATCH_CACHE_SIZE);
+    let mut output: Vec<Result<String, String>> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line: &str = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        output.push(
+            convert_line(line, &mut cache, opts).map_err(|e| format!("line {}: {}", i + 1, e)),
+        );
     }
     output
 }
 
-fn convert_value(bases: (usize, usize), val: &str) -> String {
-    let mut is_neg: bool = false;
-    let mut use_val = val;
+// split a `<inbase> <outbase> <value>` line into its bases and value
+fn split_line(line: &str) -> Result<((isize, isize), &str), String> {
+    let mut fields = line.splitn(3, char::is_whitespace).map(str::trim);
+    let (Some(b0), Some(b1), Some(val)) = (fields.next(), fields.next(), fields.next()) else {
+        return Err(String::from("expected `<from_base> <to_base> <value>`"));
+    };
+    let parse_base =
+        |b: &str| parse_base_arg(b).map_err(|_| format!("`{}` is not a valid base", b));
+    Ok(((parse_base(b0)?, parse_base(b1)?), val))
+}
+
+fn convert_line(line: &str, cache: &mut ConvertCache, opts: &Options) -> Result<String, String> {
+    let (bases, val) = split_line(line)?;
+    let result: String = convert_cached(cache, bases, val, opts.case).map_err(|e| e.to_string())?;
+    Ok(format!(
+        "{} -> {}",
+        val,
+        format_output(result, bases.1, opts)
+    ))
+}
 
-    // handle negative values as absolute values
-    if val.chars().next().unwrap() == '-' {
-        is_neg = true;
-        use_val = &val[1..];
+// convert each `<inbase> <outbase> <value>` line of `reader` until EOF, for --repl
+// a bad line writes its error and the loop carries on with the next one
+fn repl<R: BufRead, W: Write>(reader: R, writer: &mut W, opts: &Options) -> io::Result<()> {
+    for line in reader.lines() {
+        let line: String = line?;
+        let line: &str = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let result = split_line(line).and_then(|(bases, val)| {
+            convert_bases(bases, val, opts.case)
+                .map(|v| format_output(v, bases.1, opts))
+                .map_err(|e| e.to_string())
+        });
+        match result {
+            Ok(v) => writeln!(writer, "{}", v)?,
+            Err(e) => writeln!(writer, "error: {}", e)?,
+        }
+        // show each result as soon as its line is entered
+        writer.flush()?;
     }
+    Ok(())
+}
+
+// how many --in lines are converted between progress reports
+const PROGRESS_LINES: usize = 100_000;
 
-    let conv_val = match bases {
-        // dec to base
-        (10, _) => {
-            let dec_val: usize = use_val.parse().unwrap();
-            dec_to_base(dec_val, bases.1)
+// convert each line of `reader` as one value, writing one line per input line to `writer`
+// failed lines are written as `# line n: error` comments and blank lines stay blank;
+// returns the number of lines read and how many of them failed
+fn transform<R: BufRead, W: Write>(
+    bases: (isize, isize),
+    reader: R,
+    writer: &mut W,
+    opts: &Options,
+) -> io::Result<(usize, usize)> {
+    let mut cache = ConvertCache::new(BATCH_CACHE_SIZE);
+    let (mut lines, mut failed) = (0, 0);
+    for line in reader.lines() {
+        let line: String = line?;
+        lines += 1;
+        match line.trim() {
+            "" => writeln!(writer)?,
+            val => match convert_cached(&mut cache, bases, val, opts.case) {
+                Ok(v) => writeln!(writer, "{}", format_output(v, bases.1, opts))?,
+                Err(e) => {
+                    writeln!(writer, "# line {}: {}", lines, e)?;
+                    failed += 1;
+                }
+            },
         }
-        // base to dec
-        (_, 10) => format!("{}", base_to_dec(use_val, bases.0)),
-        // base to base
-        _ => {
-            let b2d: usize = base_to_dec(use_val, bases.0);
-            dec_to_base(b2d, bases.1)
+        if lines % PROGRESS_LINES == 0 {
+            report(opts, &format!("{} lines converted", lines));
         }
+    }
+    writer.flush()?;
+    Ok((lines, failed))
+}
+
+// the base `val` is read in, detecting it from the prefix for an input base of 0
+fn in_base_of(base: isize, val: &str) -> usize {
+    match base {
+        0 => detect_base(val.trim().trim_start_matches(['-', '+'])),
+        b => b.unsigned_abs(),
+    }
+}
+
+// validate the digits of `val` in `base` without converting it; base 0 detects it from the prefix
+fn check_value(base: isize, val: &str) -> Result<(), ConvertError> {
+    let base: usize = in_base_of(base, val);
+    match base {
+        2..=MAX_BASE => check_digits(val, base),
+        _ => Err(ConvertError::InvalidBase(base)),
+    }
+}
+
+// validate each `<inbase> <outbase> <value>` line of a file, labeled by line number
+fn check_lines(text: &str) -> Vec<(String, Result<(), String>)> {
+    let mut output: Vec<(String, Result<(), String>)> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line: &str = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let result = split_line(line)
+            .and_then(|(bases, val)| check_value(bases.0, val).map_err(|e| e.to_string()));
+        output.push((format!("line {}", i + 1), result));
+    }
+    output
+}
+
+// report `OK` or the error of every checked value, then how many passed
+fn check_report(checks: &[(String, Result<(), String>)]) -> Vec<String> {
+    let mut lines: Vec<String> = checks
+        .iter()
+        .map(|(label, r)| match r {
+            Ok(()) => format!("{}: OK", label),
+            Err(e) => format!("{}: {}", label, e),
+        })
+        .collect();
+    let valid: usize = checks.iter().filter(|c| c.1.is_ok()).count();
+    lines.push(format!("{} of {} valid", valid, checks.len()));
+    lines
+}
+
+// explain an integer conversion in up to two steps, expanding into decimal then dividing out
+fn explain(bases: (usize, usize), val: &str) -> Result<String, ConvertError> {
+    let in_base: usize = match bases.0 {
+        0 => detect_base(val),
+        b => b,
     };
+    let mut steps: Vec<String> = Vec::new();
+    if in_base != 10 {
+        steps.push(explain_base_to_dec(val, in_base)?);
+    }
+    if bases.1 != 10 || in_base == 10 {
+        steps.push(explain_dec_to_base(base_to_dec(val, in_base)?, bases.1)?);
+    }
+    Ok(steps.join("\n"))
+}
 
-    match is_neg {
-        true => String::from("-") + &conv_val,
-        false => conv_val,
+// quote and escape a string as a JSON string literal
+fn json_string(s: &str) -> String {
+    let mut output = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
     }
+    output.push('"');
+    output
 }
 
-fn main() {
-    let args = get_args();
-    let bases: (usize, usize) = (args[0].parse().unwrap(), args[1].parse().unwrap());
-    println!("{}", convert_value(bases, &args[2]));
+// render one conversion as a JSON object with either an output or error field
+fn json_result(val: &str, bases: (isize, isize), result: &Result<String, ConvertError>) -> String {
+    let outcome: String = match result {
+        Ok(v) => format!("\"output\":{}", json_string(v)),
+        Err(e) => format!("\"error\":{}", json_string(&e.to_string())),
+    };
+    format!(
+        "{{\"input\":{},\"in_base\":{},\"out_base\":{},{}}}",
+        json_string(val),
+        bases.0,
+        bases.1,
+        outcome
+    )
+}
+
+// several values are emitted as an array, a single value as a bare object
+fn json_output(
+    vals: &[String],
+    bases: (isize, isize),
+    results: &[Result<String, ConvertError>],
+) -> String {
+    let objects: Vec<String> = vals
+        .iter()
+        .zip(results)
+        .map(|(v, r)| json_result(v, bases, r))
+        .collect();
+    match objects.len() {
+        1 => objects[0].clone(),
+        _ => format!("[{}]", objects.join(",")),
+    }
+}
+
+fn main() -> std::process::ExitCode {
+    let (opts, args) = get_args();
+    if let Some(path) = &opts.file {
+        let text: String = match fs::read_to_string(path) {
+            Ok(t) => t,
+            Err(e) => {
+                report(&opts, &format!("{} could not be read\n{}", path, e));
+                return ExitCode::Failure.into();
+            }
+        };
+        if opts.check_only {
+            let checks = check_lines(&text);
+            println!("{}", check_report(&checks).join("\n"));
+            return ExitCode::success_if(checks.iter().all(|c| c.1.is_ok())).into();
+        }
+        let mut failed: bool = false;
+        for line in convert_lines(&text, &opts) {
+            match line {
+                Ok(v) => println!("{}", v),
+                Err(e) => {
+                    report(&opts, &e);
+                    failed = true;
+                }
+            }
+        }
+        return ExitCode::success_if(!failed).into();
+    }
+    if opts.ord.is_some() || opts.chr.is_some() || opts.valid_bases.is_some() {
+        let result = match (opts.ord, opts.chr, &opts.valid_bases) {
+            (Some(c), _, _) => ord(c),
+            (_, Some(n), _) => chr(n),
+            (_, _, v) => bases_of(v.as_deref().unwrap()),
+        };
+        match result {
+            Ok(v) => println!("{}", v),
+            Err(e) => {
+                report(&opts, &e);
+                return ExitCode::Failure.into();
+            }
+        }
+        return ExitCode::Success.into();
+    }
+    if opts.repl {
+        return match repl(io::stdin().lock(), &mut io::stdout().lock(), &opts) {
+            Ok(()) => ExitCode::Success.into(),
+            Err(e) => {
+                report(&opts, &format!("input could not be read\n{}", e));
+                ExitCode::Failure.into()
+            }
+        };
+    }
+    if let Some(path) = &opts.input {
+        let bases: (isize, isize) = (args[0].parse().unwrap(), args[1].parse().unwrap());
+        let reader = match fs::File::open(path) {
+            Ok(f) => BufReader::new(f),
+            Err(e) => {
+                report(&opts, &format!("{} could not be read\n{}", path, e));
+                return ExitCode::Failure.into();
+            }
+        };
+        let mut writer: Box<dyn Write> = match &opts.output {
+            Some(out) => match fs::File::create(out) {
+                Ok(f) => Box::new(BufWriter::new(f)),
+                Err(e) => {
+                    report(&opts, &format!("{} could not be written\n{}", out, e));
+                    return ExitCode::Failure.into();
+                }
+            },
+            None => Box::new(BufWriter::new(io::stdout().lock())),
+        };
+        return match transform(bases, reader, &mut writer, &opts) {
+            Ok((lines, failed)) => {
+                report(
+                    &opts,
+                    &format!("{} of {} lines converted", lines - failed, lines),
+                );
+                ExitCode::success_if(failed == 0).into()
+            }
+            Err(e) => {
+                report(&opts, &format!("conversion stopped\n{}", e));
+                ExitCode::Failure.into()
+            }
+        };
+    }
+    if opts.csv {
+        let bases: (isize, isize) = (args[0].parse().unwrap(), args[1].parse().unwrap());
+        match csv(bases, &opts) {
+            Ok(lines) => lines.iter().for_each(|l| println!("{}", l)),
+            Err(e) => {
+                report(&opts, &e.to_string());
+                return ExitCode::from(&e).into();
+            }
+        }
+        return ExitCode::Success.into();
+    }
+    if let Some(base) = opts.table {
+        match table(base, &opts) {
+            Ok(rows) => rows.iter().for_each(|r| println!("{}", r)),
+            Err(e) => {
+                report(&opts, &e.to_string());
+                return ExitCode::from(&e).into();
+            }
+        }
+        return ExitCode::Success.into();
+    }
+    if opts.cmp {
+        let a: (&str, usize) = (&args[1], args[0].parse().unwrap());
+        let b: (&str, usize) = (&args[3], args[2].parse().unwrap());
+        return match values_equal(a, b) {
+            Ok(eq) => {
+                println!("{}", eq);
+                // like cmp(1), differing values exit with 1
+                ExitCode::success_if(eq).into()
+            }
+            Err(e) => {
+                report(&opts, &e.to_string());
+                ExitCode::from(&e).into()
+            }
+        };
+    }
+    if let Some(base) = opts.digits.or(opts.digitsum) {
+        for v in &args[1..] {
+            let result = parse_unsigned(0, v).and_then(|n| match opts.digits {
+                Some(_) => digit_count(n, base).map(|c| c.to_string()),
+                None => digit_sum_root(n, base),
+            });
+            match result {
+                Ok(line) => println!("{}", line),
+                Err(e) => {
+                    report(&opts, &e.to_string());
+                    return ExitCode::from(&e).into();
+                }
+            }
+        }
+        return ExitCode::Success.into();
+    }
+    if let Some(base) = opts.text_to_base.or(opts.base_to_text) {
+        for v in &args {
+            let result = match opts.text_to_base {
+                Some(_) => text_to_base(v, base).map(|d| apply_case(d, base, opts.case)),
+                None => base_to_text(v, base),
+            };
+            match result {
+                Ok(line) => println!("{}", line),
+                Err(e) => {
+                    report(&opts, &e.to_string());
+                    return ExitCode::from(&e).into();
+                }
+            }
+        }
+        return ExitCode::Success.into();
+    }
+    if opts.factoradic {
+        let in_base: isize = args[0].parse().unwrap();
+        for v in &args[1..] {
+            match parse_unsigned(in_base, v) {
+                Ok(n) => println!("{}", dec_to_factoradic(n)),
+                Err(e) => {
+                    report(&opts, &e.to_string());
+                    return ExitCode::from(&e).into();
+                }
+            }
+        }
+        return ExitCode::Success.into();
+    }
+    if opts.gray {
+        let in_base: isize = args[0].parse().unwrap();
+        for v in &args[1..] {
+            match gray(in_base, v) {
+                Ok(code) => println!("{}", format_output(code, 2, &opts)),
+                Err(e) => {
+                    report(&opts, &e.to_string());
+                    return ExitCode::from(&e).into();
+                }
+            }
+        }
+        return ExitCode::Success.into();
+    }
+    if let Some(out_bases) = fan_out_bases(&opts) {
+        let in_base: usize = args[0].parse().unwrap();
+        for (i, v) in args[1..].iter().enumerate() {
+            match fan_out(in_base, out_bases, v, &opts) {
+                Ok(lines) => {
+                    // a blank line between the outputs of each value
+                    if i > 0 {
+                        println!();
+                    }
+                    println!("{}", lines.join("\n"));
+                }
+                Err(e) => {
+                    report(&opts, &e.to_string());
+                    return ExitCode::from(&e).into();
+                }
+            }
+        }
+        return ExitCode::Success.into();
+    }
+    let bases: (isize, isize) = (args[0].parse().unwrap(), args[1].parse().unwrap());
+    if opts.check_only {
+        let errors: Vec<Result<(), ConvertError>> =
+            args[2..].iter().map(|v| check_value(bases.0, v)).collect();
+        let checks: Vec<(String, Result<(), String>)> = args[2..]
+            .iter()
+            .zip(&errors)
+            .map(|(v, r)| {
+                (
+                    format!("`{}`", v),
+                    r.as_ref().map_err(|e| e.to_string()).copied(),
+                )
+            })
+            .collect();
+        println!("{}", check_report(&checks).join("\n"));
+        return first_error_code(errors.iter().filter_map(|r| r.as_ref().err())).into();
+    }
+    if opts.explain {
+        if bases.0 < 0 || bases.1 < 0 {
+            report(&opts, "--explain only supports positive bases");
+            return ExitCode::InvalidBase.into();
+        }
+        for v in &args[2..] {
+            match explain((bases.0 as usize, bases.1 as usize), v) {
+                Ok(e) => println!("{}", e),
+                Err(e) => {
+                    report(&opts, &e.to_string());
+                    return ExitCode::from(&e).into();
+                }
+            }
+        }
+        return ExitCode::Success.into();
+    }
+
+    debug(&opts, &format!("bases: {} -> {}", bases.0, bases.1));
+    let mut verified: bool = true;
+    let results = run_with(args.clone(), |bases, v| {
+        // little-endian values are reversed to the usual order on the way in, and back on
+        // the way out
+        let v: String = match opts.little_endian {
+            true => reverse_digits(v, in_base_of(bases.0, v)),
+            false => v.to_string(),
+        };
+        let c: String = convert_arg(bases, &v, &opts)?;
+        if opts.verify && round_trips(bases, &v, &c) != Ok(true) {
+            report(
+                &opts,
+                &format!("warning: `{}` does not convert back to `{}`", c, v),
+            );
+            verified = false;
+        }
+        let c: String = match opts.little_endian {
+            true => reverse_digits(&c, bases.1.unsigned_abs()),
+            false => c,
+        };
+        let c: String = match (opts.spell, opts.as_literal, opts.bytes) {
+            // spelled digits are words, so the digit formatting does not apply
+            (true, _, _) => spell_digits(&c, bases.1.unsigned_abs())?,
+            (false, Some(lang), _) => source_literal(c, bases.1, lang, &opts)?,
+            (false, None, Some(order)) => byte_pairs(c, order, &opts)?,
+            (false, None, None) => format_output(c, bases.1, &opts),
+        };
+        match opts.factor {
+            true => Ok(format!("{} = {}", c, factor(bases, &v, &opts)?)),
+            false => Ok(c),
+        }
+    });
+    let results: Vec<Result<String, ConvertError>> = match results {
+        Ok(r) => r,
+        Err(e) => {
+            report(&opts, &e.to_string());
+            return ExitCode::from(&e).into();
+        }
+    };
+
+    if opts.json {
+        println!("{}", json_output(&args[2..], bases, &results));
+        return match first_error_code(results.iter().filter_map(|r| r.as_ref().err())) {
+            ExitCode::Success => ExitCode::success_if(verified).into(),
+            code => code.into(),
+        };
+    }
+
+    for result in results {
+        match result {
+            Ok(v) => println!("{}", v),
+            Err(e) => {
+                report(&opts, &e.to_string());
+                return ExitCode::from(&e).into();
+            }
+        }
+    }
+    ExitCode::success_if(verified).into()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*; // brings main scope into test scope
+    use super::*;
+
+    #[test]
+    fn json_output_test() {
+        let vals: Vec<String> = vec!["ff".to_string(), "f\"g".to_string()];
+        let results: Vec<Result<String, ConvertError>> = vals
+            .iter()
+            .map(|v| convert_bases((16, 10), v, OutputCase::Lower))
+            .collect();
+
+        let single: serde_json::Value =
+            serde_json::from_str(&json_output(&vals[..1], (16, 10), &results[..1])).unwrap();
+        assert_eq!(single["input"], "ff");
+        assert_eq!(single["in_base"], 16);
+        assert_eq!(single["out_base"], 10);
+        assert_eq!(single["output"], "255");
+
+        let multi: serde_json::Value =
+            serde_json::from_str(&json_output(&vals, (16, 10), &results)).unwrap();
+        assert_eq!(multi.as_array().unwrap().len(), 2);
+        assert_eq!(multi[0]["output"], "255");
+        assert_eq!(multi[1]["input"], "f\"g");
+        assert_eq!(
+            multi[1]["error"],
+            "`\"` at index 1 is not a valid digit in base 16"
+        );
+        assert!(multi[1].get("output").is_none());
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let vals: Vec<((isize, isize), &str, &str, bool)> = vec![
+            ((16, 10), "ff", "255", true),
+            ((16, 2), "0xFF", "11111111", true),
+            ((0, 10), "0o17", "15", true),
+            ((10, 16), "-0042", "-2a", true),
+            ((10, -2), "6", "11010", true),
+            ((16, 10), "ff", "254", false),
+            ((10, 2), "5", "100", false),
+        ];
+        for v in vals {
+            assert_eq!(round_trips(v.0, v.1, v.2), Ok(v.3));
+        }
+    }
 
     #[test]
-    fn dec_to_base_test() {
-        let vals: Vec<(usize, &str)> = vec![
-            (0, "0"),
-            (10, "a"),
-            (15, "f"),
-            (256, "100"),
-            (4660, "1234"),
-            (65535, "ffff"),
+    fn convert_file_test() {
+        let path = env::temp_dir().join(format!("converter_file_test_{}.txt", std::process::id()));
+        let text: &str = "# in out value\n16 10 ff\n\n2 16 1111_0000\n10 2 12z\n10\n  10 -2 6  \n";
+        fs::write(&path, text).unwrap();
+        let read: String = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let expected: Vec<Result<String, String>> = vec![
+            Ok("ff -> 255".to_string()),
+            Ok("1111_0000 -> f0".to_string()),
+            Err("line 5: `z` at index 2 is not a valid digit in base 10".to_string()),
+            Err("line 6: expected `<from_base> <to_base> <value>`".to_string()),
+            Ok("6 -> 11010".to_string()),
         ];
+        assert_eq!(convert_lines(&read, &Options::default()), expected);
+    }
 
+    #[test]
+    fn parse_unsigned_test() {
+        let vals: Vec<(isize, &str, u128)> = vec![
+            (0, "65535", 65535),
+            (0, "0xffff", 65535),
+            (16, "ff_ff", 65535),
+            (2, "0b0101", 5),
+        ];
         for v in vals {
-            assert_eq!(dec_to_base(v.0, 16), v.1);
+            assert_eq!(parse_unsigned(v.0, v.1), Ok(v.2));
         }
+        assert_eq!(
+            parse_unsigned(0, "0xffff").and_then(|n| digit_count(n, 16)),
+            Ok(4)
+        );
+        assert_eq!(
+            parse_unsigned(0, "9875").and_then(|n| digit_sum_root(n, 10)),
+            Ok("29 2".to_string())
+        );
     }
 
     #[test]
-    fn base_to_dec_test() {
-        let vals: Vec<(&str, usize)> = vec![
-            ("0", 0),
-            ("a", 10),
-            ("f", 15),
-            ("100", 256),
-            ("1234", 4660),
-            ("ffff", 65535),
+    fn gray_test() {
+        let vals: Vec<(isize, &str, &str)> = vec![
+            (10, "5", "111"),
+            (10, "0", "0"),
+            (16, "ff", "10000000"),
+            (0, "0b0111", "100"),
+            (10, "1_000", "1000011100"),
         ];
+        for v in vals {
+            assert_eq!(gray(v.0, v.1), Ok(v.2.to_string()));
+        }
+        assert_eq!(
+            gray(10, "-5"),
+            Err(ConvertError::InvalidDigit {
+                ch: '-',
+                index: 0,
+                base: 10
+            })
+        );
+    }
+
+    #[test]
+    fn repl_test() {
+        let input: &str = "16 10 ff\n\n10 hex 255\n10 2 12z\nbad line\n 2 -2 110 \n";
+        let mut output: Vec<u8> = Vec::new();
+        repl(input.as_bytes(), &mut output, &Options::default()).unwrap();
+        let expected: Vec<&str> = vec![
+            "255",
+            "ff",
+            "error: `z` at index 2 is not a valid digit in base 10",
+            "error: expected `<from_base> <to_base> <value>`",
+            "11010",
+        ];
+        assert_eq!(
+            String::from_utf8(output)
+                .unwrap()
+                .lines()
+                .collect::<Vec<&str>>(),
+            expected
+        );
+    }
 
+    #[test]
+    fn transform_test() {
+        let dir = env::temp_dir();
+        let in_path = dir.join(format!("converter_in_test_{}.txt", std::process::id()));
+        let out_path = dir.join(format!("converter_out_test_{}.txt", std::process::id()));
+        fs::write(&in_path, "255\n16\n\n12z\n  -10 \n").unwrap();
+
+        let reader = BufReader::new(fs::File::open(&in_path).unwrap());
+        let mut writer = BufWriter::new(fs::File::create(&out_path).unwrap());
+        let counts = transform((10, 16), reader, &mut writer, &Options::default()).unwrap();
+        drop(writer);
+        let written: String = fs::read_to_string(&out_path).unwrap();
+        fs::remove_file(&in_path).unwrap();
+        fs::remove_file(&out_path).unwrap();
+
+        assert_eq!(counts, (5, 1));
+        // every input line has its own output line
+        let expected: Vec<&str> = vec![
+            "ff",
+            "10",
+            "",
+            "# line 4: `z` at index 2 is not a valid digit in base 10",
+            "-a",
+        ];
+        assert_eq!(written.lines().collect::<Vec<&str>>(), expected);
+    }
+
+    #[test]
+    fn read_values_test() {
+        let input: &[u8] = b"255 16\n\n  4096\t-10\n";
+        let values: Vec<String> = read_values(input).unwrap();
+        assert_eq!(values, vec!["255", "16", "4096", "-10"]);
+        let outputs: Vec<String> = values
+            .iter()
+            .map(|v| convert_bases((10, 16), v, OutputCase::Lower).unwrap())
+            .collect();
+        assert_eq!(outputs, vec!["ff", "10", "1000", "-a"]);
+        assert_eq!(read_values(&b""[..]).unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn explain_test() {
+        let vals: Vec<((usize, usize), &str, &str)> = vec![
+            ((16, 10), "ff", "f*16^1 + f*16^0 = 15*16 + 15*1 = 255"),
+            (
+                (10, 16),
+                "255",
+                "255 / 16 = 15 r 15 (f)\n15 / 16 = 0 r 15 (f)\n255 = ff",
+            ),
+            (
+                (2, 8),
+                "1010",
+                "1*2^3 + 0*2^2 + 1*2^1 + 0*2^0 = 1*8 + 0 + 1*2 + 0 = 10\n\
+                 10 / 8 = 1 r 2 (2)\n1 / 8 = 0 r 1 (1)\n10 = 12",
+            ),
+            ((0, 10), "0x1f", "1*16^1 + f*16^0 = 1*16 + 15*1 = 31"),
+        ];
         for v in vals {
-            assert_eq!(base_to_dec(v.0, 16), v.1);
+            assert_eq!(explain(v.0, v.1), Ok(v.2.to_string()));
         }
     }
 
     #[test]
-    fn char_map_test() {
-        let vals: Vec<(u8, Option<char>)> = vec![
-            (0, Some('0')),
-            (5, Some('5')),
-            (10, Some('a')),
-            (15, Some('f')),
-            (100, None),
+    fn to_base_test() {
+        let vals: Vec<(&str, &str, &str)> = vec![
+            ("2", "0xff", "11111111"),
+            ("16", "42", "2a"),
+            ("10", "0b101", "5"),
+            ("10", "017", "15"),
         ];
+        for v in vals {
+            let raw: Vec<String> = vec!["--to".to_string(), v.0.to_string(), v.1.to_string()];
+            let (opts, args) = parse_flags(raw);
+            let args: Vec<String> = with_to_base(&opts, args);
+            assert_eq!(args, vec!["0", v.0, v.1]);
+            let bases: (isize, isize) = (args[0].parse().unwrap(), args[1].parse().unwrap());
+            assert_eq!(
+                convert_bases(bases, &args[2], opts.case),
+                Ok(v.2.to_string())
+            );
+        }
+    }
 
+    #[test]
+    fn reverse_test() {
+        let vals: Vec<(Vec<&str>, &str, &str)> = vec![
+            (vec!["16", "10", "255"], "ff", "255"),
+            (vec!["hex", "2", "-101"], "-5", "-101"),
+            (vec!["10", "0", "0b1010"], "10", "1010"),
+            (vec!["-2", "10", "6"], "11010", "6"),
+        ];
         for v in vals {
-            assert_eq!(char_map(v.0), v.1);
+            let raw: Vec<String> = ["--reverse"]
+                .iter()
+                .chain(&v.0)
+                .map(|a| a.to_string())
+                .collect();
+            let (opts, args) = parse_flags(raw);
+            let args: Vec<String> = with_reversed_bases(&opts, with_base_names(&opts, args));
+            let bases: (isize, isize) = (args[0].parse().unwrap(), args[1].parse().unwrap());
+            let forward: String = convert_bases(bases, &args[2], opts.case).unwrap();
+            assert_eq!(forward, v.1);
+            // converting the result the other way gives the value back, less any prefix
+            let in_base: isize = in_base_of(bases.0, &args[2]) as isize;
+            let back: String = convert_bases((bases.1, in_base), &forward, opts.case).unwrap();
+            assert_eq!(back, v.2);
         }
     }
 
     #[test]
-    fn map_char_test() {
-        let vals: Vec<(char, Option<u8>)> = vec![
-            ('a', Some(10)),
-            ('0', Some(0)),
-            ('f', Some(15)),
-            ('5', Some(5)),
-            ('z', Some(35)),
-            ('Z', Some(35)),
+    fn factor_test() {
+        let opts = Options::default();
+        let vals: Vec<((isize, isize), &str, &str)> = vec![
+            ((10, 16), "255", "3 * 5 * 11"),
+            ((10, 10), "360", "2 * 2 * 2 * 3 * 3 * 5"),
+            ((10, 2), "13", "1101"),
+            ((16, 10), "-ff", "-1 * 3 * 5 * 17"),
+            ((10, 10), "1", "1"),
+            ((10, 10), "0", "0"),
         ];
+        for v in vals {
+            assert_eq!(factor(v.0, v.1, &opts), Ok(v.2.to_string()));
+        }
+        let upper = Options {
+            case: OutputCase::Upper,
+            ..Default::default()
+        };
+        assert_eq!(
+            factor((10, 16), "2873", &upper),
+            Ok("D * D * 11".to_string())
+        );
+        assert_eq!(
+            factor((10, 16), "2.5", &opts),
+            Err(ConvertError::InvalidDigit {
+                ch: '.',
+                index: 1,
+                base: 10
+            })
+        );
+        // too large for a u128, whether or not the `bignum` feature converts it
+        for (base, val) in [(10, "9".repeat(45)), (16, "f".repeat(33))] {
+            assert_eq!(
+                factor((base, 16), &val, &opts),
+                Err(ConvertError::Overflow {
+                    input: val.clone(),
+                    base: base as usize
+                })
+            );
+        }
+    }
 
+    #[test]
+    fn convert_signed_test() {
+        let vals: Vec<((isize, isize), &str, u32, &str)> = vec![
+            ((16, 10), "ffffffff", 32, "-1"),
+            ((16, 10), "80000000", 32, "-2147483648"),
+            ((16, 10), "7fffffff", 32, "2147483647"),
+            ((0, 16), "0xfe", 8, "-2"),
+            ((2, -10), "11111111", 8, "19"),
+        ];
         for v in vals {
-            assert_eq!(map_char(v.0), v.1);
+            let opts = Options {
+                signed: Some(v.2),
+                ..Options::default()
+            };
+            assert_eq!(convert_arg(v.0, v.1, &opts), Ok(v.3.to_string()));
         }
     }
 
     #[test]
-    fn convert_value_test() {
-        let vals: Vec<((usize, usize), &str, &str)> = vec![
-            ((10, 10), "100", "100"),
-            ((10, 16), "10", "a"),
-            ((10, 16), "4660", "1234"),
-            ((10, 8), "668", "1234"),
-            ((8, 10), "1234", "668"),
-            ((8, 16), "100", "40"),
-            ((16, 10), "0xffff", "65535"),
-            ((10, 16), "-10", "-a"),
-            ((10, 30), "1000", "13a"),
-            ((30, 10), "13a", "1000"),
+    fn convert_mod_bits_test() {
+        let vals: Vec<((isize, isize), &str, u32, &str)> = vec![
+            ((10, 10), "256", 8, "0"),
+            ((10, 10), "511", 8, "255"),
+            ((10, 16), "511", 8, "ff"),
+            ((16, 2), "0x1234", 4, "100"),
+            ((10, 10), "-1", 16, "65535"),
+            // the sign is not read as part of a detected prefix
+            ((0, 10), "-0x1", 8, "255"),
         ];
+        for v in vals {
+            let opts = Options {
+                mod_bits: Some(v.2),
+                ..Options::default()
+            };
+            assert_eq!(convert_arg(v.0, v.1, &opts), Ok(v.3.to_string()));
+        }
+    }
 
+    #[test]
+    fn convert_saturate_test() {
+        let vals: Vec<((isize, isize), &str, u128, &str)> = vec![
+            ((10, 10), "200", 255, "200"),
+            ((10, 16), "255", 255, "ff"),
+            ((10, 16), "500", 255, "ff"),
+            ((10, 2), "-5", 255, "0"),
+            ((0, 10), "0x1234", 1000, "1000"),
+        ];
         for v in vals {
-            assert_eq!(convert_value(v.0, v.1), v.2);
+            let opts = Options {
+                saturate: Some(v.2),
+                ..Options::default()
+            };
+            assert_eq!(convert_arg(v.0, v.1, &opts), Ok(v.3.to_string()));
         }
     }
-}
 
-/*
-    TODO:
-    - better error handling
-    - check all valid chars
-*/
+    #[test]
+    fn unsigned_test() {
+        let (opts, args) = parse_flags(
+            ["--unsigned", "10", "2", "-5"]
+                .iter()
+                .map(|a| a.to_string())
+                .collect(),
+        );
+        assert!(opts.unsigned);
+        assert_eq!(
+            convert_arg((10, 2), &args[2], &opts),
+            Err(ConvertError::Negative("-5".to_string()))
+        );
+        assert_eq!(convert_arg((10, 2), "5", &opts), Ok("101".to_string()));
+        // without the flag a negative value converts as usual
+        assert_eq!(
+            convert_arg((10, 2), "-5", &Options::default()),
+            Ok("-101".to_string())
+        );
+    }
+
+    #[test]
+    fn width_test() {
+        let (opts, _) = parse_flags(
+            ["--width", "4", "16", "10", "00ff"]
+                .iter()
+                .map(|a| a.to_string())
+                .collect(),
+        );
+        assert_eq!(opts.width, Some(4));
+        let vals: Vec<((isize, isize), &str, &str)> = vec![
+            ((16, 10), "00ff", "255"),
+            ((16, 10), "-0x00ff", "-255"),
+            ((0, 10), "0b0101", "5"),
+            ((-2, 10), "1101", "-3"),
+        ];
+        for v in vals {
+            assert_eq!(convert_arg(v.0, v.1, &opts), Ok(v.2.to_string()));
+        }
+        assert_eq!(
+            convert_arg((16, 10), "ff", &opts),
+            Err(ConvertError::WidthMismatch {
+                input: "ff".to_string(),
+                expected: 4,
+                found: 2
+            })
+        );
+        assert_eq!(
+            convert_arg((16, 10), "000ff", &opts).map_err(|e| e.to_string()),
+            Err("`000ff` has 5 digits, but exactly 4 are required".to_string())
+        );
+    }
+
+    #[test]
+    fn strict_width_test() {
+        let (loose, _) = parse_flags(vec!["--width".to_string(), "4".to_string()]);
+        let (strict, _) = parse_flags(
+            ["--width", "4", "--strict-width"]
+                .iter()
+                .map(|a| a.to_string())
+                .collect(),
+        );
+        assert!(strict.strict_width);
+        // strict and non-strict handling of `00ff`, then other bases and values
+        let vals: Vec<((isize, isize), &str, &str, &str)> = vec![
+            ((16, 16), "00ff", "ff", "00ff"),
+            ((16, 10), "00ff", "255", "00255"),
+            ((16, 2), "00ff", "11111111", "0000000011111111"),
+            ((16, 16), "-0x00ff", "-ff", "-00ff"),
+            ((10, 10), "001.5", "1.5", "001.5"),
+            ((10, 16), "1234", "4d2", "04d2"),
+        ];
+        for v in vals {
+            assert_eq!(convert_arg(v.0, v.1, &loose), Ok(v.2.to_string()));
+            assert_eq!(convert_arg(v.0, v.1, &strict), Ok(v.3.to_string()));
+        }
+        assert_eq!(
+            convert_arg((2, -2), "0011", &strict),
+            Err(ConvertError::InvalidNegativeBase(-2))
+        );
+    }
+
+    #[test]
+    fn verbosity_test() {
+        let vals: Vec<(Vec<&str>, Verbosity, Vec<&str>)> = vec![
+            (
+                vec!["16", "10", "ff"],
+                Verbosity::Normal,
+                vec!["16", "10", "ff"],
+            ),
+            (
+                vec!["--quiet", "16", "10", "ff"],
+                Verbosity::Quiet,
+                vec!["16", "10", "ff"],
+            ),
+            (
+                vec!["16", "10", "ff", "--verbose"],
+                Verbosity::Verbose,
+                vec!["16", "10", "ff"],
+            ),
+            (
+                vec!["--verbose", "--pad", "4", "2", "8", "11"],
+                Verbosity::Verbose,
+                vec!["2", "8", "11"],
+            ),
+            (
+                vec!["--verbose", "--quiet", "2", "8", "11"],
+                Verbosity::Quiet,
+                vec!["2", "8", "11"],
+            ),
+        ];
+        for v in vals {
+            let raw: Vec<String> = v.0.iter().map(|a| a.to_string()).collect();
+            let (opts, args) = parse_flags(raw);
+            assert_eq!(opts.verbosity, v.1);
+            assert_eq!(args, v.2);
+        }
+    }
+
+    #[test]
+    fn thousands_test() {
+        let opts = Options {
+            thousands: true,
+            group: Some(4),
+            ..Default::default()
+        };
+        let vals: Vec<(&str, isize, &str)> = vec![
+            ("100", 10, "100"),
+            ("1000", 10, "1,000"),
+            ("1000000", 10, "1,000,000"),
+            ("-1234567", 10, "-1,234,567"),
+            ("1234.5678", 10, "1,234.5678"),
+            // other output bases keep --group
+            ("11111111", 2, "1111_1111"),
+        ];
+        for v in vals {
+            assert_eq!(format_output(v.0.to_string(), v.1, &opts), v.2);
+        }
+    }
+
+    #[test]
+    fn group_char_test() {
+        let raw: Vec<String> = ["16", "16", "ffff", "--group", "2", "--group-char", "'"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        let (opts, args) = parse_flags(raw);
+        assert_eq!(opts.group_char, Some('\''));
+        let output: String = convert_bases((16, 16), &args[2], opts.case).unwrap();
+        let grouped: String = format_output(output, 16, &opts);
+        assert_eq!(grouped, "ff'ff");
+        // the grouped output reads back as the same value
+        assert_eq!(
+            convert_bases((16, 10), &grouped, opts.case),
+            Ok("65535".to_string())
+        );
+    }
+
+    #[test]
+    fn exit_code_test() {
+        let vals: Vec<(ConvertError, ExitCode, u8)> = vec![
+            (ConvertError::InvalidBase(63), ExitCode::InvalidBase, 3),
+            (
+                ConvertError::InvalidNegativeBase(-1),
+                ExitCode::InvalidBase,
+                3,
+            ),
+            (
+                ConvertError::PrefixBaseMismatch {
+                    prefix: 16,
+                    base: 8,
+                },
+                ExitCode::InvalidBase,
+                3,
+            ),
+            (
+                ConvertError::InvalidBaseArg("ten".to_string()),
+                ExitCode::InvalidBase,
+                3,
+            ),
+            (ConvertError::MissingArgs(2), ExitCode::Usage, 2),
+            (
+                ConvertError::InvalidDigit {
+                    ch: 'g',
+                    index: 0,
+                    base: 16,
+                },
+                ExitCode::InvalidDigit,
+                4,
+            ),
+            (
+                ConvertError::Overflow {
+                    input: "f".repeat(40),
+                    base: 16,
+                },
+                ExitCode::Overflow,
+                5,
+            ),
+            (
+                ConvertError::OutOfRange {
+                    value: 256,
+                    bits: 8,
+                },
+                ExitCode::Overflow,
+                5,
+            ),
+            (ConvertError::RomanRange(4000), ExitCode::Overflow, 5),
+            (ConvertError::Empty, ExitCode::Failure, 1),
+            (ConvertError::ZeroDenominator, ExitCode::Failure, 1),
+        ];
+        for v in vals {
+            assert_eq!(ExitCode::from(&v.0), v.1);
+            assert_eq!(v.1 as u8, v.2);
+        }
+
+        let errors: Vec<ConvertError> = vec![ConvertError::Empty, ConvertError::InvalidBase(1)];
+        assert_eq!(first_error_code(errors.iter()), ExitCode::Failure);
+        assert_eq!(first_error_code(errors[1..].iter()), ExitCode::InvalidBase);
+        assert_eq!(first_error_code(errors[2..].iter()), ExitCode::Success);
+        assert_eq!(ExitCode::success_if(false), ExitCode::Failure);
+    }
+
+    #[test]
+    fn bases_of_test() {
+        let bases = |r: std::ops::RangeInclusive<usize>| -> String {
+            r.map(|b| b.to_string()).collect::<Vec<String>>().join(" ")
+        };
+        assert_eq!(bases_of("123"), Ok(bases(4..=36)));
+        assert_eq!(bases_of("ff"), Ok(bases(16..=36)));
+        assert_eq!(bases_of("zz"), Ok("36".to_string()));
+        assert_eq!(
+            bases_of("f!"),
+            Err("`f!` is not a valid value in any base 2-36".to_string())
+        );
+    }
+
+    #[test]
+    fn ord_chr_test() {
+        let vals: Vec<(char, u8, &str)> = vec![
+            ('0', 0, "0 (U+0030)"),
+            ('a', 10, "10 (U+0061)"),
+            ('z', 35, "35 (U+007A)"),
+            ('A', 36, "36 (U+0041)"),
+            ('Z', 61, "61 (U+005A)"),
+        ];
+        for v in vals {
+            assert_eq!(ord(v.0), Ok(v.2.to_string()));
+            assert_eq!(chr(v.1), Ok(v.0.to_string()));
+        }
+        assert_eq!(
+            ord('#'),
+            Err("`#` is not a digit; digits: 0-9, a-z and A-Z".to_string())
+        );
+        assert_eq!(
+            chr(62),
+            Err("62 is not a digit value; values: 0-61".to_string())
+        );
+
+        // --ord takes a single char
+        assert_eq!(
+            flag_value::<char>("--ord", Some("ab".to_string())),
+            Err("ab is not a valid value for --ord".to_string())
+        );
+    }
+
+    #[test]
+    fn format_output_test() {
+        let opts = Options {
+            pad: Some(4),
+            group: Some(2),
+            subscript: true,
+            ..Default::default()
+        };
+        let vals: Vec<(&str, isize, &str)> = vec![
+            ("ff", 16, "00_ff₁₆"),
+            ("-101", 2, "-01_01₂"),
+            ("11010", -2, "1_10_10₋₂"),
+        ];
+        for v in vals {
+            assert_eq!(format_output(v.0.to_string(), v.1, &opts), v.2);
+        }
+    }
+
+    #[test]
+    fn source_literal_test() {
+        let rust = Options {
+            suffix: Some("u32".to_string()),
+            ..Default::default()
+        };
+        let c = Options {
+            pad: Some(4),
+            suffix: Some("UL".to_string()),
+            ..Default::default()
+        };
+        let vals: Vec<(&str, isize, SourceLang, &Options, &str)> = vec![
+            ("ff", 16, SourceLang::Rust, &rust, "0xffu32"),
+            ("ff", 16, SourceLang::C, &c, "0x00ffUL"),
+            ("17", 8, SourceLang::C, &c, "00017UL"),
+            ("17", 8, SourceLang::Rust, &rust, "0o17u32"),
+        ];
+        for v in vals {
+            assert_eq!(
+                source_literal(v.0.to_string(), v.1, v.2, v.3),
+                Ok(v.4.to_string())
+            );
+        }
+        assert_eq!(
+            source_literal("11".to_string(), -2, SourceLang::Rust, &rust),
+            Err(ConvertError::InvalidNegativeBase(-2))
+        );
+        assert_eq!(
+            parse_flags(vec!["--as-literal".to_string(), "c".to_string()])
+                .0
+                .as_literal,
+            Some(SourceLang::C)
+        );
+    }
+
+    #[test]
+    fn byte_pairs_test() {
+        let opts = Options::default();
+        let padded = Options {
+            pad: Some(8),
+            ..Default::default()
+        };
+        let vals: Vec<(&str, ByteOrder, &Options, &str)> = vec![
+            ("11223344", ByteOrder::Big, &opts, "11 22 33 44"),
+            ("11223344", ByteOrder::Little, &opts, "44 33 22 11"),
+            ("ff", ByteOrder::Big, &padded, "00 00 00 ff"),
+            ("ff", ByteOrder::Little, &padded, "ff 00 00 00"),
+        ];
+        for v in vals {
+            assert_eq!(byte_pairs(v.0.to_string(), v.1, v.2), Ok(v.3.to_string()));
+        }
+        assert_eq!(
+            parse_flags(vec!["--bytes".to_string(), "le".to_string()])
+                .0
+                .bytes,
+            Some(ByteOrder::Little)
+        );
+        assert_eq!(
+            byte_order("--bytes", "middle".to_string()),
+            Err("middle is not a valid value for --bytes; use big or little".to_string())
+        );
+    }
+
+    #[test]
+    fn color_test() {
+        let opts = Options {
+            group: Some(4),
+            color: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            format_output("11111010".to_string(), 2, &opts),
+            "\x1b[36m1111\x1b[0m_\x1b[33m1010\x1b[0m"
+        );
+        let thousands = Options {
+            thousands: true,
+            ..opts
+        };
+        assert_eq!(
+            format_output("1234567".to_string(), 10, &thousands),
+            "\x1b[36m1\x1b[0m,\x1b[33m234\x1b[0m,\x1b[36m567\x1b[0m"
+        );
+
+        // piped output and NO_COLOR keep it plain
+        let vals: Vec<(bool, Option<&str>, bool, bool)> = vec![
+            (true, None, true, true),
+            (true, Some(""), true, true),
+            (true, None, false, false),
+            (true, Some("1"), true, false),
+            (false, None, true, false),
+        ];
+        for v in vals {
+            assert_eq!(color_enabled(v.0, v.1.map(Into::into), v.2), v.3);
+        }
+        let piped = Options {
+            color: color_enabled(true, None, false),
+            group: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(
+            format_output("11111010".to_string(), 2, &piped),
+            "1111_1010"
+        );
+    }
+
+    #[test]
+    fn fan_out_test() {
+        let raw: Vec<String> = ["10", "255", "--to", "2,8,16"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        let (opts, args) = parse_flags(raw);
+        assert_eq!(fan_out_bases(&opts), Some(&[2, 8, 16][..]));
+        assert_eq!(with_to_base(&opts, args.clone()), args);
+        assert_eq!(
+            fan_out(10, &[2, 8, 16], &args[1], &opts),
+            Ok(vec![
+                "base 2: 11111111".to_string(),
+                "base 8: 377".to_string(),
+                "base 16: ff".to_string(),
+            ])
+        );
+
+        let opts = Options {
+            case: OutputCase::Upper,
+            ..Default::default()
+        };
+        assert_eq!(
+            fan_out(0, &[10, 62], "-0xff", &opts),
+            Ok(vec![
+                "base 10: -255".to_string(),
+                "base 62: -47".to_string()
+            ])
+        );
+        assert!(fan_out(10, &[2, 16], "25a", &opts).is_err());
+
+        // a single base is the autodetect form
+        let (opts, _) = parse_flags(vec!["--to".to_string(), "16".to_string()]);
+        assert_eq!(fan_out_bases(&opts), None);
+    }
+
+    #[test]
+    fn table_test() {
+        let (opts, _) = parse_flags(
+            ["--table", "2", "--count", "5"]
+                .iter()
+                .map(|a| a.to_string())
+                .collect(),
+        );
+        assert_eq!(
+            table(2, &opts),
+            Ok(vec![
+                "0    0".to_string(),
+                "1    1".to_string(),
+                "2   10".to_string(),
+                "3   11".to_string(),
+                "4  100".to_string(),
+            ])
+        );
+
+        let opts = Options {
+            start: Some(9),
+            end: Some(11),
+            case: OutputCase::Upper,
+            ..Default::default()
+        };
+        assert_eq!(
+            table(16, &opts),
+            Ok(vec![
+                " 9  9".to_string(),
+                "10  A".to_string(),
+                "11  B".to_string()
+            ])
+        );
+        assert_eq!(table(16, &Options::default()).unwrap().len(), 16);
+        assert!(table(63, &Options::default()).is_err());
+    }
+
+    #[test]
+    fn csv_test() {
+        let (opts, args) = parse_flags(
+            ["--csv", "10", "16", "--range", "0", "255"]
+                .iter()
+                .map(|a| a.to_string())
+                .collect(),
+        );
+        assert_eq!(args, vec!["10", "16"]);
+        assert_eq!(opts.range, Some((0, 255)));
+        let lines: Vec<String> = csv((10, 16), &opts).unwrap();
+        assert_eq!(lines.len(), 257);
+        assert_eq!(lines[..4], ["base 10,base 16", "0,0", "1,1", "2,2"]);
+        assert_eq!(lines[11], "10,a");
+        assert_eq!(lines[256], "255,ff");
+
+        // the columns may be any two bases, and a field with a comma is quoted
+        let opts = Options {
+            range: Some((1023, 1024)),
+            thousands: true,
+            case: OutputCase::Upper,
+            ..Default::default()
+        };
+        assert_eq!(
+            csv((2, 10), &opts),
+            Ok(vec![
+                "base 2,base 10".to_string(),
+                "1111111111,\"1,023\"".to_string(),
+                "10000000000,\"1,024\"".to_string(),
+            ])
+        );
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv((10, 16), &Options::default()).unwrap().len(), 17);
+        assert_eq!(
+            csv((10, -2), &Options::default()),
+            Err(ConvertError::InvalidNegativeBase(-2))
+        );
+        assert!(csv((10, 63), &Options::default()).is_err());
+    }
+
+    #[test]
+    fn check_only_test() {
+        let checks: Vec<(String, Result<(), String>)> = ["ff", "fg", "0xff", "-1_0"]
+            .iter()
+            .map(|v| {
+                (
+                    format!("`{}`", v),
+                    check_value(16, v).map_err(|e| e.to_string()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            check_report(&checks),
+            vec![
+                "`ff`: OK",
+                "`fg`: `g` at index 1 is not a valid digit in base 16",
+                "`0xff`: OK",
+                "`-1_0`: OK",
+                "3 of 4 valid",
+            ]
+        );
+
+        let text: &str = "# batch\n16 10 ff\n2 10 102\n\n0 2 0b11\n10 2\n";
+        assert_eq!(
+            check_report(&check_lines(text)),
+            vec![
+                "line 2: OK",
+                "line 3: `2` at index 2 is not a valid digit in base 2",
+                "line 5: OK",
+                "line 6: expected `<from_base> <to_base> <value>`",
+                "2 of 4 valid",
+            ]
+        );
+        assert_eq!(check_value(99, "1"), Err(ConvertError::InvalidBase(99)));
+    }
+
+    #[test]
+    fn base_names_test() {
+        let vals: Vec<(Vec<&str>, Vec<&str>, &str)> = vec![
+            (vec!["hex", "dec", "ff"], vec!["16", "10", "ff"], "255"),
+            (vec!["oct", "bin", "17"], vec!["8", "2", "17"], "1111"),
+            // only the base positions are names, a value of `dec` stays a value
+            (vec!["dec", "hex", "dec"], vec!["10", "16", "dec"], ""),
+        ];
+        for v in vals {
+            let (opts, args) = parse_flags(v.0.iter().map(|a| a.to_string()).collect());
+            let args: Vec<String> = with_base_names(&opts, args);
+            assert_eq!(args, v.1);
+            if !v.2.is_empty() {
+                let bases: (isize, isize) = (args[0].parse().unwrap(), args[1].parse().unwrap());
+                assert_eq!(
+                    convert_bases(bases, &args[2], opts.case),
+                    Ok(v.2.to_string())
+                );
+            }
+        }
+
+        let (opts, args) = parse_flags(
+            ["--cmp", "hex", "ff", "bin", "11111111"]
+                .iter()
+                .map(|a| a.to_string())
+                .collect(),
+        );
+        assert_eq!(
+            with_base_names(&opts, args),
+            vec!["16", "ff", "2", "11111111"]
+        );
+
+        let (opts, args) = parse_flags(
+            ["hex", "ff", "--to", "bin,oct,dec"]
+                .iter()
+                .map(|a| a.to_string())
+                .collect(),
+        );
+        assert_eq!(fan_out_bases(&opts), Some(&[2, 8, 10][..]));
+        assert_eq!(with_base_names(&opts, args), vec!["16", "ff"]);
+        assert_eq!(split_line("hex dec ff"), Ok(((16, 10), "ff")));
+    }
+}
@@ -0,0 +1,259 @@
+/*
+    Mixed-radix numbers, where each position has its own radix, most significant first
+    Time is the usual example: radices [24, 60, 60] turn 3661 seconds into 1:01:01
+    A string with one digit char per position converts between two such schemes
+*/
+
+use crate::{digit_to_char, map_char_in_base, ConvertError, MAX_BASE};
+
+/// Splits `value` into one digit per radix of `radices`, most significant first,
+/// eg. 3661 with [24, 60, 60] -> [1, 1, 1]
+///
+/// Every radix must be at least 2, and `value` must be below their product; a larger
+/// value is an `Overflow` reported in the leading radix.
+pub fn to_mixed_radix(value: u128, radices: &[usize]) -> Result<Vec<usize>, ConvertError> {
+    if radices.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+    if let Some(r) = radices.iter().find(|r| **r < 2) {
+        return Err(ConvertError::InvalidBase(*r));
+    }
+
+    let mut digits: Vec<usize> = Vec::with_capacity(radices.len());
+    let mut cur_val: u128 = value;
+    for r in radices.iter().rev() {
+        // the remainder is below the radix, so it fits a usize
+        digits.push((cur_val % *r as u128) as usize);
+        cur_val /= *r as u128;
+    }
+    if cur_val > 0 {
+        return Err(ConvertError::Overflow {
+            input: value.to_string(),
+            base: radices[0],
+        });
+    }
+    digits.reverse();
+    Ok(digits)
+}
+
+/// Joins `digits`, one per radix of `radices` and most significant first, back into
+/// their value, eg. [1, 1, 1] with [24, 60, 60] -> 3661
+pub fn from_mixed_radix(digits: &[usize], radices: &[usize]) -> Result<u128, ConvertError> {
+    if digits.len() != radices.len() {
+        return Err(ConvertError::RadixCount {
+            digits: digits.len(),
+            radices: radices.len(),
+        });
+    }
+    if digits.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+
+    let mut out_val: u128 = 0;
+    for (index, (d, r)) in digits.iter().zip(radices).enumerate() {
+        if *r < 2 {
+            return Err(ConvertError::InvalidBase(*r));
+        }
+        if d >= r {
+            return Err(ConvertError::MixedRadixDigit {
+                digit: *d,
+                index,
+                radix: *r,
+            });
+        }
+        out_val = out_val
+            .checked_mul(*r as u128)
+            .and_then(|v| v.checked_add(*d as u128))
+            .ok_or_else(|| ConvertError::Overflow {
+                input: format!("{:?}", digits),
+                base: radices[0],
+            })?;
+    }
+    Ok(out_val)
+}
+
+/// Converts `value`, one digit char per radix of `in_radices`, to one digit char per radix
+/// of `out_radices`, eg. `21` with [3, 4] is 9, and 9 with [2, 6] -> `13`
+///
+/// Each digit must be valid in its own position's radix, and every radix must be 2-62 so
+/// its digits have chars; letters are case-insensitive in radices up to 36.
+pub fn convert_nonuniform(
+    value: &str,
+    in_radices: &[usize],
+    out_radices: &[usize],
+) -> Result<String, ConvertError> {
+    if let Some(r) = in_radices
+        .iter()
+        .chain(out_radices)
+        .find(|r| **r > MAX_BASE)
+    {
+        return Err(ConvertError::InvalidBase(*r));
+    }
+    let count: usize = value.chars().count();
+    if count != in_radices.len() {
+        return Err(ConvertError::RadixCount {
+            digits: count,
+            radices: in_radices.len(),
+        });
+    }
+
+    let mut digits: Vec<usize> = Vec::with_capacity(count);
+    for (index, (c, r)) in value.chars().zip(in_radices).enumerate() {
+        match map_char_in_base(c, *r) {
+            Some(d) => digits.push(usize::from(d)),
+            // a radix below 2 is reported by from_mixed_radix
+            None if *r < 2 => digits.push(0),
+            None => {
+                return Err(ConvertError::InvalidDigit {
+                    ch: c,
+                    index,
+                    base: *r,
+                })
+            }
+        }
+    }
+    let out_digits: Vec<usize> =
+        to_mixed_radix(from_mixed_radix(&digits, in_radices)?, out_radices)?;
+    // each digit is below its radix, at most 62, so it has a char
+    Ok(out_digits
+        .iter()
+        .map(|d| digit_to_char(*d as u8).unwrap())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixed_radix_test() {
+        let time: &[usize] = &[24, 60, 60];
+        // pounds, shillings and pence, with 20 shillings to the pound and 12 pence to the shilling
+        let lsd: &[usize] = &[1000, 20, 12];
+        let vals: Vec<(u128, &[usize], Vec<usize>)> = vec![
+            (3661, time, vec![1, 1, 1]),
+            (0, time, vec![0, 0, 0]),
+            (86399, time, vec![23, 59, 59]),
+            (1000, lsd, vec![4, 3, 4]),
+            (239, lsd, vec![0, 19, 11]),
+            (5, &[2, 2, 2], vec![1, 0, 1]),
+        ];
+        for v in vals {
+            assert_eq!(to_mixed_radix(v.0, v.1), Ok(v.2.clone()));
+            assert_eq!(from_mixed_radix(&v.2, v.1), Ok(v.0));
+        }
+
+        // repeating one radix is ordinary base conversion, zero-padded to the width
+        for n in 0..5000u128 {
+            let digits: Vec<usize> = to_mixed_radix(n, &[16; 4]).unwrap();
+            let expected: Vec<usize> = format!("{:04x}", n)
+                .chars()
+                .map(|c| c.to_digit(16).unwrap() as usize)
+                .collect();
+            assert_eq!(digits, expected);
+            assert_eq!(from_mixed_radix(&digits, &[16; 4]), Ok(n));
+        }
+
+        assert_eq!(
+            to_mixed_radix(86400, time),
+            Err(ConvertError::Overflow {
+                input: "86400".to_string(),
+                base: 24
+            })
+        );
+        assert_eq!(
+            to_mixed_radix(1, &[60, 1]),
+            Err(ConvertError::InvalidBase(1))
+        );
+        assert_eq!(to_mixed_radix(1, &[]), Err(ConvertError::Empty));
+        assert_eq!(
+            from_mixed_radix(&[1, 60, 0], time),
+            Err(ConvertError::MixedRadixDigit {
+                digit: 60,
+                index: 1,
+                radix: 60
+            })
+        );
+        assert_eq!(
+            from_mixed_radix(&[1, 1], time),
+            Err(ConvertError::RadixCount {
+                digits: 2,
+                radices: 3
+            })
+        );
+    }
+
+    #[test]
+    fn convert_nonuniform_test() {
+        // a two-position scheme, a digit 0-2 then a digit 0-3, to a binary digit then a
+        // digit 0-5; both hold 6 values or more, so every value fits
+        let (scheme_in, scheme_out): (&[usize], &[usize]) = (&[3, 4], &[2, 6]);
+        let vals: Vec<(&str, &str)> = vec![
+            ("00", "00"),
+            ("03", "03"),
+            ("10", "04"),
+            ("11", "05"),
+            ("21", "13"),
+            ("23", "15"),
+        ];
+        for v in vals {
+            assert_eq!(
+                convert_nonuniform(v.0, scheme_in, scheme_out),
+                Ok(v.1.to_string())
+            );
+            assert_eq!(
+                convert_nonuniform(v.1, scheme_out, scheme_in),
+                Ok(v.0.to_string())
+            );
+        }
+        // letter digits, and a uniform scheme matching ordinary base conversion
+        assert_eq!(
+            convert_nonuniform("Bb", &[12, 12], &[16, 16]),
+            Ok("8f".to_string())
+        );
+        assert_eq!(
+            convert_nonuniform("ff", &[16, 16], &[2; 8]),
+            Ok("11111111".to_string())
+        );
+
+        // each digit is checked against its own position's radix
+        assert_eq!(
+            convert_nonuniform("14", scheme_in, scheme_out),
+            Err(ConvertError::InvalidDigit {
+                ch: '4',
+                index: 1,
+                base: 4
+            })
+        );
+        assert_eq!(
+            convert_nonuniform("30", scheme_in, scheme_out),
+            Err(ConvertError::InvalidDigit {
+                ch: '3',
+                index: 0,
+                base: 3
+            })
+        );
+        assert_eq!(
+            convert_nonuniform("2", scheme_in, scheme_out),
+            Err(ConvertError::RadixCount {
+                digits: 1,
+                radices: 2
+            })
+        );
+        assert_eq!(
+            convert_nonuniform("23", scheme_in, &[2, 2]),
+            Err(ConvertError::Overflow {
+                input: "11".to_string(),
+                base: 2
+            })
+        );
+        assert_eq!(
+            convert_nonuniform("11", &[63, 2], scheme_out),
+            Err(ConvertError::InvalidBase(63))
+        );
+        assert_eq!(
+            convert_nonuniform("01", &[1, 2], scheme_out),
+            Err(ConvertError::InvalidBase(1))
+        );
+    }
+}
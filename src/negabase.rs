@@ -0,0 +1,184 @@
+/*
+    Negative bases, eg. base -2 ("negabinary")
+    Every integer has a digit string without needing a sign, eg. 6 in base -2 -> 11010
+*/
+
+use crate::{
+    base_to_dec, dec_to_base, digit_to_char, digit_value, read_literal, shift_index,
+    strip_separators, trim_whitespace, ConvertError, MAX_BASE,
+};
+
+// negative bases mirror the positive range, -2 to -MAX_BASE
+fn check_negabase(base: isize) -> Result<(), ConvertError> {
+    match base < 0 && (2..=MAX_BASE).contains(&base.unsigned_abs()) {
+        true => Ok(()),
+        false => Err(ConvertError::InvalidNegativeBase(base)),
+    }
+}
+
+/// Renders `value` in the negative `base`, eg. 6 in base -2 -> `11010`
+pub fn dec_to_negabase(value: i128, base: isize) -> Result<String, ConvertError> {
+    check_negabase(base)?;
+    if value == 0 {
+        return Ok("0".to_string());
+    }
+
+    let big_base = base as i128;
+    let mut output: Vec<char> = Vec::new();
+    let mut cur_val: i128 = value;
+    while cur_val != 0 {
+        let mut res = (cur_val / big_base, cur_val % big_base);
+        // keep the remainder in 0..|base| by borrowing from the quotient
+        if res.1 < 0 {
+            res = (res.0 + 1, res.1 - big_base);
+        }
+        cur_val = res.0;
+        output.push(digit_to_char(res.1 as u8).unwrap());
+    }
+    Ok(output.into_iter().rev().collect())
+}
+
+/// Parses `in_val` written in the negative `base`, eg. `11` in base -2 -> -1
+pub fn negabase_to_dec(in_val: &str, base: isize) -> Result<i128, ConvertError> {
+    check_negabase(base)?;
+    if in_val.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+
+    let digit_base: usize = base.unsigned_abs();
+    let mut out_val: i128 = 0;
+    for (i, c) in in_val.chars().enumerate() {
+        let char_val: i128 = digit_value(c, i, digit_base)? as i128;
+        out_val = out_val
+            .checked_mul(base as i128)
+            .and_then(|v| v.checked_add(char_val))
+            .ok_or_else(|| ConvertError::Overflow {
+                input: in_val.to_string(),
+                base: digit_base,
+            })?;
+    }
+    Ok(out_val)
+}
+
+/// Converts the integer literal `val` between `bases`, either of which may be negative
+///
+/// Only values in a positive base carry a `-` sign; negative base values never need one.
+/// Surrounding whitespace and digit separators are ignored either way, and a positive
+/// input base is read like `convert_value` reads it, so 0 detects the base from a prefix.
+pub fn convert_negabase(bases: (isize, isize), val: &str) -> Result<String, ConvertError> {
+    let dec_val: i128 = match bases.0 {
+        b if b < 0 => negabase_to_dec(&strip_separators(trim_whitespace(val)), b)?,
+        b => {
+            let (is_neg, base, digits, offset) = read_literal(val, b as usize)?;
+            let magnitude: u128 = base_to_dec(&digits, base).map_err(|e| shift_index(e, offset))?;
+            let overflow = || ConvertError::Overflow {
+                input: val.to_string(),
+                base,
+            };
+            match is_neg {
+                true => 0i128.checked_sub_unsigned(magnitude).ok_or_else(overflow)?,
+                false => i128::try_from(magnitude).map_err(|_| overflow())?,
+            }
+        }
+    };
+
+    match bases.1 {
+        b if b < 0 => dec_to_negabase(dec_val, b),
+        b => {
+            let magnitude: String = dec_to_base(dec_val.unsigned_abs(), b as usize)?;
+            match dec_val < 0 {
+                true => Ok(String::from("-") + &magnitude),
+                false => Ok(magnitude),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negabinary_test() {
+        let vals: Vec<(i128, &str)> = vec![
+            (0, "0"),
+            (1, "1"),
+            (2, "110"),
+            (3, "111"),
+            (6, "11010"),
+            (-1, "11"),
+            (-2, "10"),
+            (-6, "1110"),
+        ];
+
+        for v in vals {
+            assert_eq!(dec_to_negabase(v.0, -2).unwrap(), v.1);
+            assert_eq!(negabase_to_dec(v.1, -2).unwrap(), v.0);
+        }
+    }
+
+    #[test]
+    fn negadecimal_test() {
+        let vals: Vec<(i128, &str)> = vec![
+            (10, "190"),
+            (-10, "10"),
+            (15, "195"),
+            (-5, "15"),
+            (100, "100"),
+            (-1234, "2846"),
+        ];
+
+        for v in vals {
+            assert_eq!(dec_to_negabase(v.0, -10).unwrap(), v.1);
+            assert_eq!(negabase_to_dec(v.1, -10).unwrap(), v.0);
+        }
+    }
+
+    #[test]
+    fn convert_negabase_test() {
+        let vals: Vec<((isize, isize), &str, &str)> = vec![
+            ((10, -2), "6", "11010"),
+            ((10, -2), "-6", "1110"),
+            ((-2, 10), "11", "-1"),
+            ((-2, 16), "11010", "6"),
+            ((16, -10), "-ff", "1865"),
+            ((-10, -2), "190", "11110"),
+            // read like any other literal first
+            ((10, -2), "1_000", "10000111000"),
+            ((0, -2), "0x10", "10000"),
+            ((10, -2), " 6", "11010"),
+            ((16, -2), "-0xf", "110001"),
+            ((-2, 10), "1_1010\n", "6"),
+        ];
+
+        for v in vals {
+            assert_eq!(convert_negabase(v.0, v.1).unwrap(), v.2);
+        }
+
+        for n in -300..=300 {
+            let neg: String = dec_to_negabase(n, -3).unwrap();
+            assert_eq!(negabase_to_dec(&neg, -3).unwrap(), n);
+        }
+
+        assert_eq!(
+            dec_to_negabase(1, -1),
+            Err(ConvertError::InvalidNegativeBase(-1))
+        );
+        assert_eq!(
+            convert_negabase((16, -2), "-fg"),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 2,
+                base: 16
+            })
+        );
+        assert_eq!(
+            negabase_to_dec("2", -2),
+            Err(ConvertError::InvalidDigit {
+                ch: '2',
+                index: 0,
+                base: 2
+            })
+        );
+    }
+}
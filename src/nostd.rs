@@ -0,0 +1,166 @@
+/*
+    Integer conversions that need neither the standard library nor a heap
+    Everything here uses only `core`: it is all a `--no-default-features --features nostd`
+    build has, and `base_to_dec`/`dec_to_base` wrap it otherwise.
+    Digits are written into a caller-provided buffer.
+*/
+
+use crate::{digit_to_char, map_char_in_base, MAX_BASE};
+use core::fmt;
+
+/// Errors from the buffer-based conversions; unlike `ConvertError` they never
+/// own a heap-allocated string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoreError {
+    /// `index` is the zero-based position of `ch` in the digits
+    InvalidDigit {
+        ch: char,
+        index: usize,
+        base: usize,
+    },
+    InvalidBase(usize),
+    Empty,
+    /// The value does not fit in a u128
+    Overflow,
+    /// The buffer is shorter than the digits of the value
+    BufferTooSmall,
+}
+
+// names a digit char in an error message: an ascii char in backticks, anything else by
+// its code point, as combining marks and the like would otherwise render attached to
+// the quotes or not at all
+struct DigitChar(char);
+
+impl fmt::Display for DigitChar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.is_ascii() {
+            true => write!(f, "`{}`", self.0),
+            false => write!(f, "non-ASCII character U+{:04X}", u32::from(self.0)),
+        }
+    }
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CoreError::InvalidDigit { ch, index, base } => write!(
+                f,
+                "{} at index {} is not a valid digit in base {}",
+                DigitChar(*ch),
+                index,
+                base
+            ),
+            CoreError::InvalidBase(b) => {
+                write!(
+                    f,
+                    "{} is not a valid base; bases: 2-{} allowed",
+                    b, MAX_BASE
+                )
+            }
+            CoreError::Empty => write!(f, "value is empty"),
+            CoreError::Overflow => write!(f, "value is too large to convert"),
+            CoreError::BufferTooSmall => write!(f, "buffer is too small for the digits"),
+        }
+    }
+}
+
+impl core::error::Error for CoreError {}
+
+/// Parses the unsigned digits `digits` written in `base` into their value,
+/// eg. `ff` in base 16 -> 255; no prefix, sign or separators are accepted
+pub fn parse_digits(digits: &str, base: usize) -> Result<u128, CoreError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(CoreError::InvalidBase(base));
+    }
+    if digits.is_empty() {
+        return Err(CoreError::Empty);
+    }
+
+    let mut value: u128 = 0;
+    for (index, ch) in digits.chars().enumerate() {
+        let digit: u8 =
+            map_char_in_base(ch, base).ok_or(CoreError::InvalidDigit { ch, index, base })?;
+        value = value
+            .checked_mul(base as u128)
+            .and_then(|v| v.checked_add(digit.into()))
+            .ok_or(CoreError::Overflow)?;
+    }
+    Ok(value)
+}
+
+/// Writes the digits of `value` in `base` to the end of `buf`, returning them as a str,
+/// eg. 255 in base 16 -> `ff`
+///
+/// A 128 byte buffer holds any u128 in any base.
+pub fn write_digits(value: u128, base: usize, buf: &mut [u8]) -> Result<&str, CoreError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(CoreError::InvalidBase(base));
+    }
+
+    // fill from the back, least significant digit first
+    let mut start: usize = buf.len();
+    let mut cur_val: u128 = value;
+    loop {
+        start = start.checked_sub(1).ok_or(CoreError::BufferTooSmall)?;
+        // the remainder is below base, so it always maps to an ascii digit
+        buf[start] = digit_to_char((cur_val % base as u128) as u8).unwrap() as u8;
+        cur_val /= base as u128;
+        if cur_val == 0 {
+            break;
+        }
+    }
+    // every byte written is an ascii digit
+    Ok(core::str::from_utf8(&buf[start..]).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    // the crate itself may be no_std, but the tests run with std
+    extern crate std;
+    use super::*;
+    use std::vec;
+    use std::vec::Vec;
+
+    #[test]
+    fn write_digits_test() {
+        let vals: Vec<(u128, usize, &str)> = vec![
+            (255, 16, "ff"),
+            (0, 2, "0"),
+            (3843, 62, "ZZ"),
+            (u128::MAX, 36, "f5lxx1zz5pnorynqglhzmsp33"),
+        ];
+        for v in vals {
+            let mut buf = [0u8; 128];
+            assert_eq!(write_digits(v.0, v.1, &mut buf), Ok(v.2));
+            assert_eq!(parse_digits(v.2, v.1), Ok(v.0));
+        }
+
+        // the widest case, u128::MAX in binary, fills the whole buffer
+        let mut buf = [0u8; 128];
+        assert_eq!(write_digits(u128::MAX, 2, &mut buf).map(str::len), Ok(128));
+        let mut small = [0u8; 2];
+        assert_eq!(
+            write_digits(256, 16, &mut small),
+            Err(CoreError::BufferTooSmall)
+        );
+        assert_eq!(
+            write_digits(1, 63, &mut buf),
+            Err(CoreError::InvalidBase(63))
+        );
+    }
+
+    #[test]
+    fn parse_digits_test() {
+        assert_eq!(parse_digits("FF", 16), Ok(255));
+        assert_eq!(parse_digits("", 16), Err(CoreError::Empty));
+        assert_eq!(
+            parse_digits("1g", 16),
+            Err(CoreError::InvalidDigit {
+                ch: 'g',
+                index: 1,
+                base: 16
+            })
+        );
+        assert_eq!(parse_digits(&"f".repeat(33), 16), Err(CoreError::Overflow));
+    }
+}
@@ -0,0 +1,155 @@
+/*
+    A parsed integer value, for library users who want a number rather than a string
+    Numbers compare by value, whatever base they were written in
+    A Literal also keeps the base detected from its prefix, so it can be `str::parse`d
+*/
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{dec_to_base, detect_base, parse_signed, split_sign, ConvertError};
+
+/// An integer read from a literal in some base, eg. `Number::from_base("ff", 16)`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Number {
+    pub value: i128,
+}
+
+impl Number {
+    /// Parses the (optionally negative) literal `s` written in `base`
+    pub fn from_base(s: &str, base: usize) -> Result<Number, ConvertError> {
+        let (is_neg, magnitude) = parse_signed(s, base)?;
+        let overflow = || ConvertError::Overflow {
+            input: s.to_string(),
+            base,
+        };
+        let value: i128 = match is_neg {
+            // the magnitude of i128::MIN is one past i128::MAX
+            true => 0i128.checked_sub_unsigned(magnitude).ok_or_else(overflow)?,
+            false => i128::try_from(magnitude).map_err(|_| overflow())?,
+        };
+        Ok(Number { value })
+    }
+
+    /// Renders the value in `base`, with a leading `-` when negative
+    pub fn to_base(&self, base: usize) -> Result<String, ConvertError> {
+        let digits: String = dec_to_base(self.value.unsigned_abs(), base)?;
+        match self.value < 0 {
+            true => Ok(format!("-{}", digits)),
+            false => Ok(digits),
+        }
+    }
+}
+
+impl From<i128> for Number {
+    fn from(value: i128) -> Number {
+        Number { value }
+    }
+}
+
+// numbers display in decimal
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+/// An integer literal whose base comes from its prefix, eg. `"0xff".parse::<Literal>()`
+///
+/// `0x`, `0o` and `0b` select bases 16, 8 and 2, a leading `0` followed by a digit is
+/// octal, and anything else is decimal, see `detect_base`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Literal {
+    pub value: Number,
+    pub base: usize,
+}
+
+impl FromStr for Literal {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Literal, ConvertError> {
+        let trimmed: &str = s.trim();
+        let unsigned: &str = split_sign(trimmed).1;
+        let base: usize = detect_base(unsigned);
+        Ok(Literal {
+            value: Number::from_base(s, base)?,
+            base,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_test() {
+        let hex = Number::from_base("ff", 16).unwrap();
+        let oct = Number::from_base("377", 8).unwrap();
+        assert_eq!(hex, oct);
+        assert_eq!(hex.value, 255);
+        assert_eq!(hex.to_string(), "255");
+        assert_eq!(hex.to_base(2), Ok("11111111".to_string()));
+
+        assert_eq!(Number::from_base("+ff", 16), Ok(hex));
+        assert_eq!("+0x10".parse::<Literal>().map(|l| l.base), Ok(16));
+
+        let neg = Number::from_base("-0o20", 8).unwrap();
+        assert_eq!(neg, Number::from(-16));
+        assert_eq!(neg.to_base(16), Ok("-10".to_string()));
+        assert!(neg < oct);
+        assert!(Number::from_base("10", 16).unwrap() > Number::from_base("10", 8).unwrap());
+        assert_eq!(Number::from_base("-0", 10).unwrap(), Number::default());
+    }
+
+    #[test]
+    fn number_range_test() {
+        let max: String = i128::MAX.to_string();
+        let min: String = i128::MIN.to_string();
+        assert_eq!(Number::from_base(&max, 10).map(|n| n.value), Ok(i128::MAX));
+        assert_eq!(Number::from_base(&min, 10).map(|n| n.value), Ok(i128::MIN));
+        assert_eq!(Number::from(i128::MIN).to_base(10), Ok(min));
+
+        let vals: Vec<&str> = vec![
+            "170141183460469231731687303715884105728",
+            "-170141183460469231731687303715884105729",
+        ];
+        for v in vals {
+            assert_eq!(
+                Number::from_base(v, 10),
+                Err(ConvertError::Overflow {
+                    input: v.to_string(),
+                    base: 10
+                })
+            );
+        }
+        assert!(Number::from_base("fg", 16).is_err());
+    }
+
+    #[test]
+    fn literal_test() {
+        let vals: Vec<(&str, i128, usize)> = vec![
+            ("0xff", 255, 16),
+            ("0b1010", 10, 2),
+            ("0o17", 15, 8),
+            ("017", 15, 8),
+            ("42", 42, 10),
+            ("-0x10", -16, 16),
+            ("0", 0, 10),
+        ];
+        for v in vals {
+            let lit: Literal = v.0.parse().unwrap();
+            assert_eq!(lit.value.value, v.1);
+            assert_eq!(lit.base, v.2);
+        }
+        assert_eq!(
+            "0b102".parse::<Literal>(),
+            Err(ConvertError::InvalidDigit {
+                ch: '2',
+                index: 4,
+                base: 2
+            })
+        );
+        assert_eq!("".parse::<Literal>(), Err(ConvertError::Empty));
+    }
+}
@@ -0,0 +1,143 @@
+/*
+    Builder-style options for a conversion, so each formatting choice is named at the
+    call site instead of growing the parameter list of `convert_value`,
+    eg. ConversionOptions::new().uppercase(true).prefix(true).pad(4) renders 255 as 0x00FF
+*/
+
+use crate::{
+    apply_case, base_prefix, check_unsigned, convert_value, group_digits, pad_digits, ConvertError,
+    OutputCase,
+};
+
+/// How `convert_with` renders a converted value; the default renders it exactly as
+/// `convert_value` does
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConversionOptions {
+    case: OutputCase,
+    prefix: bool,
+    pad: Option<usize>,
+    group: Option<(usize, char)>,
+    unsigned: bool,
+}
+
+impl ConversionOptions {
+    /// Starts from the defaults: lowercase, no prefix, padding or grouping, signs allowed
+    pub fn new() -> ConversionOptions {
+        ConversionOptions::default()
+    }
+
+    /// Renders letter digits in uppercase, for output bases up to 36
+    pub fn uppercase(mut self, on: bool) -> ConversionOptions {
+        self.case = match on {
+            true => OutputCase::Upper,
+            false => OutputCase::Lower,
+        };
+        self
+    }
+
+    /// Puts the output base's `0x`/`0o`/`0b` prefix after any sign, see `base_prefix`
+    pub fn prefix(mut self, on: bool) -> ConversionOptions {
+        self.prefix = on;
+        self
+    }
+
+    /// Left-pads the integer digits with zeros to at least `width`, see `pad_digits`
+    pub fn pad(mut self, width: usize) -> ConversionOptions {
+        self.pad = Some(width);
+        self
+    }
+
+    /// Separates the integer digits into groups of `size` with `sep`, see `group_digits`
+    pub fn group(mut self, size: usize, sep: char) -> ConversionOptions {
+        self.group = Some((size, sep));
+        self
+    }
+
+    /// Rejects a negative value instead of converting it, see `check_unsigned`
+    pub fn unsigned(mut self, on: bool) -> ConversionOptions {
+        self.unsigned = on;
+        self
+    }
+
+    /// Converts `value` from base `from` to base `to` with these options applied,
+    /// padding before grouping and adding the prefix last, eg. `-0x00_ff`
+    pub fn convert_with(
+        &self,
+        value: &str,
+        from: usize,
+        to: usize,
+    ) -> Result<String, ConvertError> {
+        if self.unsigned {
+            check_unsigned(value)?;
+        }
+        let mut output: String = apply_case(convert_value((from, to), value)?, to, self.case);
+        if let Some(width) = self.pad {
+            output = pad_digits(&output, width);
+        }
+        if let Some((size, sep)) = self.group {
+            output = group_digits(&output, size, sep);
+        }
+        if self.prefix {
+            output = match output.strip_prefix('-') {
+                Some(v) => format!("-{}{}", base_prefix(to), v),
+                None => format!("{}{}", base_prefix(to), output),
+            };
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversion_options_test() {
+        let upper_prefixed = ConversionOptions::new().uppercase(true).prefix(true);
+        let vals: Vec<(ConversionOptions, &str, (usize, usize), &str)> = vec![
+            (ConversionOptions::new(), "255", (10, 16), "ff"),
+            (upper_prefixed, "255", (10, 16), "0xFF"),
+            (upper_prefixed.pad(4), "-255", (10, 16), "-0x00FF"),
+            (
+                ConversionOptions::new().pad(8).group(4, '_'),
+                "5",
+                (10, 2),
+                "0000_0101",
+            ),
+            (
+                ConversionOptions::new().group(3, ',').prefix(true),
+                "1234567",
+                (10, 10),
+                "1,234,567",
+            ),
+            (upper_prefixed, "61", (10, 62), "Z"),
+            (
+                ConversionOptions::new().unsigned(true),
+                "+12",
+                (10, 8),
+                "14",
+            ),
+        ];
+        for v in vals {
+            assert_eq!(v.0.convert_with(v.1, v.2 .0, v.2 .1), Ok(v.3.to_string()));
+        }
+
+        // the defaults match the bare functions
+        for v in ["255", "-0x1f", "10.5", "0"] {
+            assert_eq!(
+                ConversionOptions::new().convert_with(v, 0, 2),
+                convert_value((0, 2), v)
+            );
+        }
+        assert_eq!(
+            ConversionOptions::new()
+                .unsigned(true)
+                .convert_with("-5", 10, 2),
+            Err(ConvertError::Negative("-5".to_string()))
+        );
+        assert_eq!(
+            ConversionOptions::new().convert_with("12", 10, 63),
+            Err(ConvertError::InvalidBase(63))
+        );
+    }
+}
@@ -0,0 +1,95 @@
+/*
+    Exact expansions of rational numbers, with the repeating digits in brackets
+    Long division repeats once a remainder comes round again, eg. 1/6 -> 0.1(6) in base 10
+*/
+
+use crate::{dec_to_base, digit_to_char, ConvertError, MAX_BASE};
+use std::collections::HashMap;
+
+/// Longest repetend `rational_to_base` will search for before giving up
+pub const MAX_REPETEND_DIGITS: usize = 1024;
+
+/// Renders `num / den` in `base` exactly, bracketing the repeating digits of the
+/// fraction, eg. 1/3 -> `0.(3)` in base 10 and `0.(01)` in base 2, 7/2 -> `3.5`
+///
+/// Expansions that have not repeated within `MAX_REPETEND_DIGITS` fractional digits
+/// are an `Overflow`.
+pub fn rational_to_base(num: u128, den: u128, base: usize) -> Result<String, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    if den == 0 {
+        return Err(ConvertError::ZeroDenominator);
+    }
+    let overflow = || ConvertError::Overflow {
+        input: format!("{}/{}", num, den),
+        base,
+    };
+
+    let int_digits: String = dec_to_base(num / den, base)?;
+    // the fraction digit each remainder produced, to spot where the cycle starts
+    let mut seen: HashMap<u128, usize> = HashMap::new();
+    let mut frac_digits = String::new();
+    let mut rem: u128 = num % den;
+    while rem != 0 {
+        if let Some(start) = seen.get(&rem) {
+            let (fixed, repetend) = frac_digits.split_at(*start);
+            return Ok(format!("{}.{}({})", int_digits, fixed, repetend));
+        }
+        if frac_digits.len() == MAX_REPETEND_DIGITS {
+            return Err(overflow());
+        }
+        seen.insert(rem, frac_digits.len());
+        let scaled: u128 = rem.checked_mul(base as u128).ok_or_else(overflow)?;
+        // scaled / den is below base, so it always maps to a digit
+        frac_digits.push(digit_to_char((scaled / den) as u8).unwrap());
+        rem = scaled % den;
+    }
+
+    match frac_digits.is_empty() {
+        true => Ok(int_digits),
+        false => Ok(format!("{}.{}", int_digits, frac_digits)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rational_to_base_test() {
+        let vals: Vec<(u128, u128, usize, &str)> = vec![
+            (1, 3, 10, "0.(3)"),
+            (1, 2, 2, "0.1"),
+            (1, 3, 2, "0.(01)"),
+            (1, 6, 10, "0.1(6)"),
+            (1, 7, 10, "0.(142857)"),
+            (22, 7, 10, "3.(142857)"),
+            (7, 2, 10, "3.5"),
+            (6, 3, 10, "2"),
+            (0, 5, 16, "0"),
+            (1, 10, 2, "0.0(0011)"),
+            (1, 3, 16, "0.(5)"),
+            (255, 16, 16, "f.f"),
+        ];
+        for v in vals {
+            assert_eq!(rational_to_base(v.0, v.1, v.2), Ok(v.3.to_string()));
+        }
+        assert_eq!(
+            rational_to_base(1, 0, 10),
+            Err(ConvertError::ZeroDenominator)
+        );
+        assert_eq!(
+            rational_to_base(1, 3, 63),
+            Err(ConvertError::InvalidBase(63))
+        );
+        // 1/1103 in base 10 repeats every 1102 digits
+        assert_eq!(
+            rational_to_base(1, 1103, 10),
+            Err(ConvertError::Overflow {
+                input: "1/1103".to_string(),
+                base: 10
+            })
+        );
+    }
+}
@@ -0,0 +1,118 @@
+/*
+    Roman numerals as a pseudo-base
+    Standard subtractive notation over 1-3999, eg. 4 -> IV, 1994 -> MCMXCIV
+*/
+
+use crate::ConvertError;
+
+// numeral values largest first, including the subtractive pairs
+const NUMERALS: [(usize, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+/// Renders `n` as a roman numeral; only 1-3999 are representable
+pub fn dec_to_roman(n: usize) -> Result<String, ConvertError> {
+    if !(1..=3999).contains(&n) {
+        return Err(ConvertError::RomanRange(n));
+    }
+
+    let mut output = String::new();
+    let mut cur_val: usize = n;
+    for (value, numeral) in NUMERALS {
+        while cur_val >= value {
+            output.push_str(numeral);
+            cur_val -= value;
+        }
+    }
+    Ok(output)
+}
+
+/// Parses a roman numeral (either case) into its value
+///
+/// Only the canonical form is accepted, so `IIII` or `IC` are errors.
+pub fn roman_to_dec(s: &str) -> Result<usize, ConvertError> {
+    if s.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+
+    let upper: String = s.to_ascii_uppercase();
+    let mut rest: &str = &upper;
+    let mut out_val: usize = 0;
+    for (value, numeral) in NUMERALS {
+        while let Some(r) = rest.strip_prefix(numeral) {
+            out_val += value;
+            rest = r;
+        }
+    }
+
+    // leftovers are bad chars or out of order numerals, and a canonical
+    // numeral always renders back to itself
+    let invalid = || ConvertError::InvalidRoman(s.to_string());
+    if !rest.is_empty() || out_val > 3999 {
+        return Err(invalid());
+    }
+    match dec_to_roman(out_val) {
+        Ok(r) if r == upper => Ok(out_val),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dec_to_roman_test() {
+        let vals: Vec<(usize, &str)> = vec![
+            (1, "I"),
+            (4, "IV"),
+            (9, "IX"),
+            (14, "XIV"),
+            (40, "XL"),
+            (90, "XC"),
+            (400, "CD"),
+            (1994, "MCMXCIV"),
+            (2024, "MMXXIV"),
+            (3999, "MMMCMXCIX"),
+        ];
+
+        for v in vals {
+            assert_eq!(dec_to_roman(v.0).unwrap(), v.1);
+            assert_eq!(roman_to_dec(v.1).unwrap(), v.0);
+        }
+
+        assert_eq!(dec_to_roman(0), Err(ConvertError::RomanRange(0)));
+        assert_eq!(dec_to_roman(4000), Err(ConvertError::RomanRange(4000)));
+    }
+
+    #[test]
+    fn roman_to_dec_test() {
+        assert_eq!(roman_to_dec("mcmxciv").unwrap(), 1994);
+        assert_eq!(roman_to_dec(""), Err(ConvertError::Empty));
+        for s in ["IIII", "IC", "VX", "MMMM", "XIIV", "ABC", "I I"] {
+            assert_eq!(
+                roman_to_dec(s),
+                Err(ConvertError::InvalidRoman(s.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn roman_round_trip_test() {
+        for n in 1..=3999 {
+            assert_eq!(roman_to_dec(&dec_to_roman(n).unwrap()).unwrap(), n);
+        }
+    }
+}
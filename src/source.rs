@@ -0,0 +1,154 @@
+/*
+    Converted values written as integer literals for Rust or C source, for code generation
+    The languages differ in their octal prefix, 0o17 in Rust and 017 in C, and in their
+    type suffixes, eg. 0xffu32 in Rust and 0xffUL in C
+*/
+
+use crate::{base_prefix, map_char_in_base, ConvertError};
+use std::str::FromStr;
+
+/// The language whose integer literal rules `to_source_literal` follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceLang {
+    Rust,
+    C,
+}
+
+impl FromStr for SourceLang {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<SourceLang, ConvertError> {
+        match s.to_ascii_lowercase().as_str() {
+            "rust" | "rs" => Ok(SourceLang::Rust),
+            "c" => Ok(SourceLang::C),
+            _ => Err(ConvertError::UnknownLanguage(s.to_string())),
+        }
+    }
+}
+
+// the integer suffixes of each language; C suffixes may be in either case
+const RUST_SUFFIXES: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+const C_SUFFIXES: [&str; 7] = ["u", "l", "ul", "lu", "ll", "ull", "llu"];
+
+/// Writes the integer `value`, already in `base`, as a `lang` source literal with an optional
+/// type `suffix`, eg. `ff` in base 16 -> `0xffu32` in Rust or `0xffUL` in C
+///
+/// Only bases 2, 8, 10 and 16 have literals; C writes octal with a leading `0` and binary
+/// with the C23 `0b` prefix. A leading `-` is kept in front.
+pub fn to_source_literal(
+    value: &str,
+    base: usize,
+    lang: SourceLang,
+    suffix: Option<&str>,
+) -> Result<String, ConvertError> {
+    if ![2, 8, 10, 16].contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    let (sign, digits) = match value.strip_prefix('-') {
+        Some(v) => ("-", v),
+        None => ("", value),
+    };
+    if digits.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+    // literals are integers, so a radix point is as invalid in one as any other non-digit
+    let sign_len: usize = value.len() - digits.len();
+    for (i, c) in digits.chars().enumerate() {
+        if map_char_in_base(c, base).is_none() {
+            return Err(ConvertError::InvalidDigit {
+                ch: c,
+                index: sign_len + i,
+                base,
+            });
+        }
+    }
+
+    let suffix: &str = suffix.unwrap_or("");
+    let valid_suffix: bool = suffix.is_empty()
+        || match lang {
+            SourceLang::Rust => RUST_SUFFIXES.contains(&suffix),
+            SourceLang::C => C_SUFFIXES.contains(&suffix.to_ascii_lowercase().as_str()),
+        };
+    if !valid_suffix {
+        return Err(ConvertError::InvalidSuffix(suffix.to_string()));
+    }
+
+    let prefix: &str = match (lang, base) {
+        // a lone 0 is already octal in C
+        (SourceLang::C, 8) if digits == "0" => "",
+        (SourceLang::C, 8) => "0",
+        (_, b) => base_prefix(b),
+    };
+    Ok(format!("{}{}{}{}", sign, prefix, digits, suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_source_literal_test() {
+        let vals: Vec<(&str, usize, SourceLang, Option<&str>, &str)> = vec![
+            ("ff", 16, SourceLang::Rust, None, "0xff"),
+            ("ff", 16, SourceLang::Rust, Some("u32"), "0xffu32"),
+            ("ff", 16, SourceLang::C, None, "0xff"),
+            ("ff", 16, SourceLang::C, Some("UL"), "0xffUL"),
+            ("17", 8, SourceLang::Rust, None, "0o17"),
+            ("17", 8, SourceLang::C, Some("u"), "017u"),
+            ("0", 8, SourceLang::C, None, "0"),
+            ("1010", 2, SourceLang::Rust, Some("u8"), "0b1010u8"),
+            ("1010", 2, SourceLang::C, None, "0b1010"),
+            ("255", 10, SourceLang::Rust, Some("i64"), "255i64"),
+            ("-80", 16, SourceLang::C, Some("ll"), "-0x80ll"),
+            ("FF", 16, SourceLang::Rust, None, "0xFF"),
+        ];
+        for v in vals {
+            assert_eq!(to_source_literal(v.0, v.1, v.2, v.3), Ok(v.4.to_string()));
+        }
+
+        assert_eq!("Rust".parse::<SourceLang>(), Ok(SourceLang::Rust));
+        assert_eq!("c".parse::<SourceLang>(), Ok(SourceLang::C));
+        assert_eq!(
+            "go".parse::<SourceLang>(),
+            Err(ConvertError::UnknownLanguage("go".to_string()))
+        );
+        assert_eq!(
+            to_source_literal("ff", 16, SourceLang::Rust, Some("UL")),
+            Err(ConvertError::InvalidSuffix("UL".to_string()))
+        );
+        assert_eq!(
+            to_source_literal("ff", 16, SourceLang::C, Some("u32")),
+            Err(ConvertError::InvalidSuffix("u32".to_string()))
+        );
+        assert_eq!(
+            to_source_literal("zz", 36, SourceLang::C, None),
+            Err(ConvertError::InvalidBase(36))
+        );
+        assert_eq!(
+            to_source_literal("1.8", 16, SourceLang::Rust, None),
+            Err(ConvertError::InvalidDigit {
+                ch: '.',
+                index: 1,
+                base: 16
+            })
+        );
+        assert_eq!(
+            to_source_literal("zz", 16, SourceLang::Rust, None),
+            Err(ConvertError::InvalidDigit {
+                ch: 'z',
+                index: 0,
+                base: 16
+            })
+        );
+        assert_eq!(
+            to_source_literal("-12", 2, SourceLang::C, None),
+            Err(ConvertError::InvalidDigit {
+                ch: '2',
+                index: 2,
+                base: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,94 @@
+/*
+    Digits spelled out for reading values aloud, using the NATO phonetic alphabet
+    for letter digits, eg. ff in base 16 -> foxtrot foxtrot
+    Uppercase letters only differ from lowercase above base 36, where they are `capital`
+*/
+
+use crate::{map_char_in_base, ConvertError, MAX_BASE};
+
+// the spoken word for each digit value below 36, the uppercase letters repeat them
+const DIGIT_WORDS: [&str; 36] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "alfa",
+    "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india", "juliett", "kilo",
+    "lima", "mike", "november", "oscar", "papa", "quebec", "romeo", "sierra", "tango", "uniform",
+    "victor", "whiskey", "xray", "yankee", "zulu",
+];
+
+/// Spells each digit of `value` in `base` as a word, eg. `ff` in base 16 -> `foxtrot foxtrot`
+///
+/// A leading `-` is spelled `minus` and a single radix point `point`.
+pub fn spell_digits(value: &str, base: usize) -> Result<String, ConvertError> {
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(ConvertError::InvalidBase(base));
+    }
+    if value.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+
+    let mut words: Vec<String> = Vec::new();
+    let mut seen_point: bool = false;
+    for (i, c) in value.chars().enumerate() {
+        let word: String = match (i, c) {
+            (0, '-') => "minus".to_string(),
+            (_, '.') if !seen_point => {
+                seen_point = true;
+                "point".to_string()
+            }
+            _ => match map_char_in_base(c, base) {
+                Some(d @ 36..) => format!("capital {}", DIGIT_WORDS[usize::from(d - 26)]),
+                Some(d) => DIGIT_WORDS[usize::from(d)].to_string(),
+                None => {
+                    return Err(ConvertError::InvalidDigit {
+                        ch: c,
+                        index: i,
+                        base,
+                    })
+                }
+            },
+        };
+        words.push(word);
+    }
+    Ok(words.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spell_digits_test() {
+        let vals: Vec<(&str, usize, &str)> = vec![
+            ("ff", 16, "foxtrot foxtrot"),
+            ("FF", 16, "foxtrot foxtrot"),
+            ("1a", 16, "one alfa"),
+            ("409", 10, "four zero nine"),
+            ("-12.5", 10, "minus one two point five"),
+            ("zZ", 62, "zulu capital zulu"),
+            ("Aa", 62, "capital alfa alfa"),
+        ];
+        for v in vals {
+            assert_eq!(spell_digits(v.0, v.1), Ok(v.2.to_string()));
+        }
+        assert_eq!(
+            spell_digits("fg", 16),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 1,
+                base: 16
+            })
+        );
+        assert_eq!(spell_digits("", 16), Err(ConvertError::Empty));
+        // only one radix point is spelled, the second is reported
+        assert_eq!(
+            spell_digits("1.2.3", 10),
+            Err(ConvertError::InvalidDigit {
+                ch: '.',
+                index: 3,
+                base: 10
+            })
+        );
+        for base in [0, 1, 63] {
+            assert_eq!(spell_digits("1", base), Err(ConvertError::InvalidBase(base)));
+        }
+    }
+}
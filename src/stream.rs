@@ -0,0 +1,138 @@
+/*
+    Streaming conversion of very long values
+    Digits are fed in chunks and folded into a growing integer with Horner's method,
+    acc = acc * base + digit, so no powers of the base are ever computed
+*/
+
+use crate::bytes::{bytes_to_base, mul_add};
+use crate::{digit_value, ConvertError, DIGIT_SEPARATORS, MAX_BASE};
+
+/// Converts an unsigned integer too long to hold in memory as a string, fed a chunk
+/// at a time; memory is bounded by the size of the value, not of its digits
+///
+/// Separators and whitespace are skipped, since long values are often wrapped across
+/// lines. The first bad digit is reported by `finish`, indexed from the first chunk.
+#[derive(Debug)]
+pub struct BaseConverter {
+    bases: (usize, usize),
+    // the value so far as little-endian bytes
+    acc: Vec<u8>,
+    // chars seen so far, for the index of a bad digit
+    index: usize,
+    digits: usize,
+    error: Option<ConvertError>,
+}
+
+impl BaseConverter {
+    /// Starts a conversion from `bases.0` to `bases.1`
+    pub fn new(bases: (usize, usize)) -> BaseConverter {
+        let error: Option<ConvertError> = [bases.0, bases.1]
+            .into_iter()
+            .find(|b| !(2..=MAX_BASE).contains(b))
+            .map(ConvertError::InvalidBase);
+        BaseConverter {
+            bases,
+            acc: Vec::new(),
+            index: 0,
+            digits: 0,
+            error,
+        }
+    }
+
+    /// Adds the next chunk of digits, most significant first
+    pub fn feed(&mut self, chunk: &str) {
+        for c in chunk.chars() {
+            let index: usize = self.index;
+            self.index += 1;
+            if self.error.is_some() || c.is_ascii_whitespace() || DIGIT_SEPARATORS.contains(&c) {
+                continue;
+            }
+            match digit_value(c, index, self.bases.0) {
+                Ok(d) => mul_add(&mut self.acc, self.bases.0, d as usize),
+                Err(e) => self.error = Some(e),
+            }
+            self.digits += 1;
+        }
+    }
+
+    /// Renders the value fed so far in the output base
+    pub fn finish(self) -> Result<String, ConvertError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        if self.digits == 0 {
+            return Err(ConvertError::Empty);
+        }
+        let big_endian: Vec<u8> = self.acc.into_iter().rev().collect();
+        match bytes_to_base(&big_endian, self.bases.1)? {
+            s if s.is_empty() => Ok(String::from("0")),
+            s => Ok(s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_converter_test() {
+        let vals: Vec<((usize, usize), Vec<&str>, &str)> = vec![
+            ((16, 10), vec!["ff", "ff"], "65535"),
+            ((2, 16), vec!["1111", "_0000", "\n"], "f0"),
+            ((10, 2), vec!["0", "00"], "0"),
+            ((62, 10), vec!["Z", "z"], "3817"),
+        ];
+        for v in vals {
+            let mut conv = BaseConverter::new(v.0);
+            for chunk in v.1 {
+                conv.feed(chunk);
+            }
+            assert_eq!(conv.finish(), Ok(v.2.to_string()));
+        }
+
+        let mut conv = BaseConverter::new((16, 10));
+        conv.feed("ff");
+        conv.feed("fg");
+        conv.feed("h");
+        assert_eq!(
+            conv.finish(),
+            Err(ConvertError::InvalidDigit {
+                ch: 'g',
+                index: 3,
+                base: 16
+            })
+        );
+        assert_eq!(
+            BaseConverter::new((16, 63)).finish(),
+            Err(ConvertError::InvalidBase(63))
+        );
+        assert_eq!(
+            BaseConverter::new((16, 10)).finish(),
+            Err(ConvertError::Empty)
+        );
+    }
+
+    #[test]
+    fn base_converter_long_value_test() {
+        // far beyond a u128, fed in uneven chunks
+        let value: String = "9876543210".repeat(50);
+        let mut conv = BaseConverter::new((10, 36));
+        for chunk in value.as_bytes().chunks(37) {
+            conv.feed(std::str::from_utf8(chunk).unwrap());
+        }
+        let streamed: String = conv.finish().unwrap();
+
+        let mut back = BaseConverter::new((36, 10));
+        back.feed(&streamed);
+        assert_eq!(back.finish(), Ok(value.clone()));
+
+        // a value that fits matches the one-shot conversion
+        let mut conv = BaseConverter::new((10, 36));
+        conv.feed(&value[..30]);
+        assert_eq!(conv.finish(), crate::convert_value((10, 36), &value[..30]));
+
+        #[cfg(feature = "bignum")]
+        assert_eq!(Ok(streamed), crate::convert_value((10, 36), &value));
+    }
+}
@@ -0,0 +1,107 @@
+/*
+    Balanced ternary, where each digit is -1, 0 or 1
+    The -1 digit is written `T`, so negatives need no sign, eg. 5 -> 1TT, -4 -> TT
+*/
+
+use crate::ConvertError;
+
+/// Renders `n` in balanced ternary, eg. 5 -> `1TT`
+pub fn dec_to_balanced_ternary(n: i128) -> String {
+    if n == 0 {
+        return "0".to_string();
+    }
+
+    let mut output = String::new();
+    let mut cur_val: i128 = n;
+    while cur_val != 0 {
+        // a remainder of 2 is written as -1 with a carry into the next digit
+        let rem: i128 = cur_val.rem_euclid(3);
+        cur_val = cur_val.div_euclid(3);
+        match rem {
+            0 => output.insert(0, '0'),
+            1 => output.insert(0, '1'),
+            _ => {
+                output.insert(0, 'T');
+                cur_val += 1;
+            }
+        }
+    }
+    output
+}
+
+/// Parses a balanced ternary literal of `T`/`t`, `0` and `1` digits, eg. `TT` -> -4
+pub fn balanced_ternary_to_dec(s: &str) -> Result<i128, ConvertError> {
+    if s.is_empty() {
+        return Err(ConvertError::Empty);
+    }
+
+    let mut out_val: i128 = 0;
+    for (i, c) in s.chars().enumerate() {
+        let char_val: i128 = match c {
+            'T' | 't' => -1,
+            '0' => 0,
+            '1' => 1,
+            _ => {
+                return Err(ConvertError::InvalidDigit {
+                    ch: c,
+                    index: i,
+                    base: 3,
+                })
+            }
+        };
+        // adding the digit before the last multiple keeps values like i128::MIN,
+        // whose prefix times 3 is just past the range, from tripping the check
+        out_val = out_val
+            .checked_add(char_val)
+            .and_then(|v| out_val.checked_mul(2)?.checked_add(v))
+            .ok_or_else(|| ConvertError::Overflow {
+                input: s.to_string(),
+                base: 3,
+            })?;
+    }
+    Ok(out_val)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balanced_ternary_test() {
+        let vals: Vec<(i128, &str)> = vec![
+            (0, "0"),
+            (1, "1"),
+            (2, "1T"),
+            (3, "10"),
+            (5, "1TT"),
+            (8, "10T"),
+            (-1, "T"),
+            (-4, "TT"),
+            (-5, "T11"),
+        ];
+
+        for v in vals {
+            assert_eq!(dec_to_balanced_ternary(v.0), v.1);
+            assert_eq!(balanced_ternary_to_dec(v.1).unwrap(), v.0);
+        }
+
+        assert_eq!(balanced_ternary_to_dec("1tt").unwrap(), 5);
+        assert_eq!(
+            balanced_ternary_to_dec("12"),
+            Err(ConvertError::InvalidDigit {
+                ch: '2',
+                index: 1,
+                base: 3
+            })
+        );
+        assert_eq!(balanced_ternary_to_dec(""), Err(ConvertError::Empty));
+    }
+
+    #[test]
+    fn balanced_ternary_round_trip_test() {
+        for n in (-500..=500).chain([i128::MIN, i128::MAX]) {
+            let bt: String = dec_to_balanced_ternary(n);
+            assert_eq!(balanced_ternary_to_dec(&bt).unwrap(), n);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+// the command line end to end: `run` in-process, then the built binary's output and exit code
+
+use converter::{run, ConvertError};
+use std::process::{Command, Output, Stdio};
+
+fn args(s: &str) -> Vec<String> {
+    s.split_whitespace().map(String::from).collect()
+}
+
+fn convert(s: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_converter"))
+        .args(args(s))
+        .stdin(Stdio::null())
+        .output()
+        .expect("the converter binary runs")
+}
+
+#[test]
+fn run_converts_test() {
+    assert_eq!(
+        run(args("10 16 255 4096")),
+        Ok(vec![Ok("ff".to_string()), Ok("1000".to_string())])
+    );
+    assert_eq!(
+        run(args("16 10 fg")),
+        Ok(vec![Err(ConvertError::InvalidDigit {
+            ch: 'g',
+            index: 1,
+            base: 16
+        })])
+    );
+    assert_eq!(run(args("10")), Err(ConvertError::MissingArgs(1)));
+}
+
+#[test]
+fn binary_success_test() {
+    let out: Output = convert("hex dec ff 7f");
+    assert_eq!(out.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "255\n127\n");
+    assert!(out.stderr.is_empty());
+
+    // base 1 is unary
+    let out: Output = convert("10 1 5");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "11111\n");
+
+    // text goes to its UTF-8 bytes and back
+    let out: Output = convert("--text-to-base 16 Hi");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "4869\n");
+    let out: Output = convert("--base-to-text 16 4869");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "Hi\n");
+
+    // negabase values are read like any other literal
+    let out: Output = convert("10 -2 1_000 +6");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "10000111000\n11010\n");
+    let out: Output = convert("0 -2 0x10");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "10000\n");
+
+    // flags format each converted value
+    let out: Output = convert("--upper --pad 4 10 16 255");
+    assert_eq!(out.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "00FF\n");
+}
+
+#[test]
+fn binary_failure_test() {
+    // an invalid digit exits with 4 and names the digit on stderr
+    let out: Output = convert("16 10 fg");
+    assert_eq!(out.status.code(), Some(4));
+    assert!(out.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8_lossy(&out.stderr).trim(),
+        "`g` at index 1 is not a valid digit in base 16"
+    );
+
+    // too few args exit with 2 and print the usage
+    let out: Output = convert("10");
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&out.stderr).starts_with("Usage: convert"));
+
+    // the values before a failing one are still printed
+    let out: Output = convert("10 1 5 2000000");
+    assert_eq!(out.status.code(), Some(5));
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "11111\n");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stderr).trim(),
+        "`2000000` in base 1 is too large to convert"
+    );
+
+    // bases are read and checked by `run`, so a bad one fails the whole run
+    let out: Output = convert("ten 16 255");
+    assert_eq!(out.status.code(), Some(3));
+    assert!(out.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8_lossy(&out.stderr).trim(),
+        "`ten` can not be parsed as a base"
+    );
+
+    let out: Output = convert("10 63 255");
+    assert_eq!(out.status.code(), Some(3));
+    assert_eq!(
+        String::from_utf8_lossy(&out.stderr).trim(),
+        "63 is not a valid base; bases: 1-62 allowed"
+    );
+
+    let out: Output = convert("--quiet 10 63 255");
+    assert_eq!(out.status.code(), Some(3));
+    assert!(out.stderr.is_empty());
+}
+
+#[test]
+fn binary_scientific_notation_test() {
+    // a whole decimal in scientific notation gets past the digit checks
+    let out: Output = convert("10 16 1e3 2.5E2");
+    assert_eq!(out.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "3e8\nfa\n");
+
+    let out: Output = convert("--check-only 10 16 1e-0 1e");
+    assert_eq!(out.status.code(), Some(4));
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "`1e-0`: OK\n`1e`: `e` at index 1 is not a valid digit in base 10\n1 of 2 valid\n"
+    );
+
+    // a fractional result is not a digit error
+    let out: Output = convert("10 16 2.5e0");
+    assert_eq!(out.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&out.stderr).trim(),
+        "`2.5e0` is not a whole number"
+    );
+}